@@ -0,0 +1,90 @@
+use crate::signal::{Level, Signal};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ArithmeticKind {
+    /// Outputs `a + b + carry_in` and the carry out. An unplugged carry in
+    /// counts as 0.
+    Adder,
+    /// Outputs `a - b - borrow_in` and the borrow out. An unplugged borrow in
+    /// counts as 0.
+    Subtractor,
+    /// Outputs whether `a` is lower than, equal to or greater than `b`.
+    Comparator,
+    /// Outputs the low and the high halves of `a * b`.
+    Multiplier,
+}
+
+/// A component computing on whole buses of `width` bits, without being
+/// built out of gates. All the operands are unsigned.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Arithmetic {
+    pub kind: ArithmeticKind,
+    pub width: u8,
+}
+
+/// Reads a carry or borrow input, which counts as 0 when unplugged.
+fn carry(signal: Option<&Signal>) -> Option<u128> {
+    match signal.map_or(Level::HighZ, |signal| signal.level(0)) {
+        Level::Low | Level::HighZ => Some(0),
+        Level::High => Some(1),
+        Level::Undefined => None,
+    }
+}
+
+impl Arithmetic {
+    pub fn evaluate(&self, inputs: &[Signal]) -> Vec<Signal> {
+        let width = self.width;
+        let undefined = || match self.kind {
+            ArithmeticKind::Adder | ArithmeticKind::Subtractor => {
+                vec![Signal::undefined(width), Signal::undefined(1)]
+            }
+            ArithmeticKind::Comparator => vec![Signal::undefined(1); 3],
+            ArithmeticKind::Multiplier => vec![Signal::undefined(width); 2],
+        };
+
+        let operand = |index: usize| {
+            let signal = inputs.get(index)?;
+            if signal.width() != width {
+                return None;
+            }
+            signal.value().map(u128::from)
+        };
+        let (Some(a), Some(b)) = (operand(0), operand(1)) else {
+            return undefined();
+        };
+        let mask = (1u128 << width) - 1;
+
+        match self.kind {
+            ArithmeticKind::Adder => {
+                let Some(carry_in) = carry(inputs.get(2)) else {
+                    return undefined();
+                };
+                let sum = a + b + carry_in;
+                vec![
+                    Signal::new(width, (sum & mask) as u64),
+                    Signal::bit(sum > mask),
+                ]
+            }
+            ArithmeticKind::Subtractor => {
+                let Some(borrow_in) = carry(inputs.get(2)) else {
+                    return undefined();
+                };
+                let difference = a.wrapping_sub(b + borrow_in);
+                vec![
+                    Signal::new(width, (difference & mask) as u64),
+                    Signal::bit(a < b + borrow_in),
+                ]
+            }
+            ArithmeticKind::Comparator => {
+                vec![Signal::bit(a < b), Signal::bit(a == b), Signal::bit(a > b)]
+            }
+            ArithmeticKind::Multiplier => {
+                let product = a * b;
+                vec![
+                    Signal::new(width, (product & mask) as u64),
+                    Signal::new(width, ((product >> width) & mask) as u64),
+                ]
+            }
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use crate::signal::{MAX_WIDTH, Signal};
+
+/// Breaks a bus into its `width` lowest bits, bit `i` going to output `i`.
+pub fn split(bus: Signal, width: u8) -> Vec<Signal> {
+    (0..width).map(|index| bus.slice(index, 1)).collect()
+}
+
+/// Gathers signals into one bus, the first one giving the lowest bits.
+pub fn merge(inputs: &[Signal]) -> Signal {
+    inputs
+        .iter()
+        .rev()
+        .copied()
+        .reduce(|high, low| low.concat(high).unwrap_or(Signal::undefined(MAX_WIDTH)))
+        .unwrap_or(Signal::floating(1))
+}
@@ -0,0 +1,45 @@
+use crate::signal::{Level, Signal};
+
+/// Segments lit for each hexadecimal digit, segment `a` being bit 0 and
+/// segment `g` bit 6.
+const HEX_SEGMENTS: [u64; 16] = [
+    0x3f, 0x06, 0x5b, 0x4f, 0x66, 0x6d, 0x7d, 0x07, 0x7f, 0x6f, 0x77, 0x7c, 0x39, 0x5e, 0x79, 0x71,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DisplayKind {
+    /// One input per segment, from `a` to `g`.
+    SevenSegment,
+    /// A single 4 bits input shown as a hexadecimal digit.
+    Hex,
+}
+
+impl DisplayKind {
+    pub fn input_names(self) -> &'static [&'static str] {
+        match self {
+            DisplayKind::SevenSegment => &["a", "b", "c", "d", "e", "f", "g"],
+            DisplayKind::Hex => &["D"],
+        }
+    }
+
+    /// The state of the segments, from `a` to `g`, for the signals on the
+    /// inputs.
+    pub fn segments(self, inputs: &[Signal]) -> [Level; 7] {
+        match self {
+            DisplayKind::SevenSegment => std::array::from_fn(|segment| {
+                inputs
+                    .get(segment)
+                    .map_or(Level::HighZ, |input| input.level(0))
+            }),
+            DisplayKind::Hex => {
+                let input = inputs.first().copied().unwrap_or(Signal::floating(4));
+                let digit = match (input.width(), input.value()) {
+                    _ if input.is_floating() => Signal::floating(7),
+                    (4, Some(digit)) => Signal::new(7, HEX_SEGMENTS[digit as usize]),
+                    _ => Signal::undefined(7),
+                };
+                std::array::from_fn(|segment| digit.level(segment as u8))
+            }
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{
+    arithmetic::Arithmetic,
+    bus,
+    memory::Memory,
+    plexer::Plexer,
+    sequential::Register,
+    signal::{Level, Signal},
+};
+
+/// What a gate computes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Operation {
+    And,
+    Or,
+    Not,
+    /// Passes its data input through while its enable input is 1, and
+    /// leaves its output floating otherwise.
+    TriState,
+}
+
+impl Operation {
+    /// The output of a gate for the signals on its inputs, the data input of
+    /// a tri-state buffer coming first.
+    pub fn evaluate(self, inputs: &[Signal]) -> Signal {
+        match (self, inputs) {
+            (Operation::And, [a, b]) => *a & *b,
+            (Operation::Or, [a, b]) => *a | *b,
+            (Operation::Not, [a]) => !*a,
+            (Operation::TriState, [data, enable]) => match enable.level(0) {
+                Level::High => *data,
+                Level::Low => Signal::floating(data.width()),
+                Level::Undefined | Level::HighZ => Signal::undefined(data.width()),
+            },
+            _ => Signal::undefined(1),
+        }
+    }
+
+    /// How many inputs a gate doing this has.
+    pub fn inputs(self) -> usize {
+        match self {
+            Operation::Not => 1,
+            Operation::And | Operation::Or | Operation::TriState => 2,
+        }
+    }
+}
+
+/// A component computing the signals on its outputs from the signals on its
+/// inputs. Registers and memories output their current contents.
+#[derive(Clone, Debug)]
+pub enum Element {
+    Gate(Operation),
+    /// Breaks a bus into its bits.
+    Splitter(u8),
+    Merger,
+    Plexer(Plexer),
+    Arithmetic(Arithmetic),
+    Memory(Memory),
+    Register(Register),
+}
+
+impl Element {
+    /// Gets one signal per input, unconnected ones floating, and returns one
+    /// signal per output.
+    pub fn evaluate(&self, inputs: &[Signal]) -> Vec<Signal> {
+        match self {
+            Element::Gate(operation) => vec![operation.evaluate(inputs)],
+            Element::Splitter(width) => bus::split(
+                inputs.first().copied().unwrap_or(Signal::floating(1)),
+                *width,
+            ),
+            Element::Merger => vec![bus::merge(inputs)],
+            Element::Plexer(plexer) => plexer.evaluate(inputs),
+            Element::Arithmetic(arithmetic) => arithmetic.evaluate(inputs),
+            Element::Memory(memory) => vec![memory.read(inputs)],
+            Element::Register(register) => register.outputs(),
+        }
+    }
+
+    /// Updates a register or a memory on a rising edge of its clock,
+    /// returning whether its contents changed.
+    pub fn clock(&mut self, inputs: &[Signal]) -> bool {
+        match self {
+            Element::Register(register) => register.clock(inputs),
+            Element::Memory(memory) => memory.clock(inputs),
+            _ => false,
+        }
+    }
+}
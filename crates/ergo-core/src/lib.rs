@@ -0,0 +1,13 @@
+//! The simulation core of ergo: signals, the components of a circuit and
+//! their evaluation, without any window or GPU. The editor is one frontend
+//! over it, tests and batch tools can be others.
+
+pub mod arithmetic;
+pub mod bus;
+pub mod display;
+pub mod element;
+pub mod memory;
+pub mod plexer;
+pub mod sequential;
+pub mod signal;
+pub mod simulation;
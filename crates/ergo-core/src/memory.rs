@@ -0,0 +1,200 @@
+use crate::signal::{Level, Signal};
+
+/// Widest address a memory takes, i.e. 64K words.
+pub const MAX_ADDRESS_WIDTH: u8 = 16;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MemoryKind {
+    /// Inputs are the address, the data in, the write enable and the clock.
+    Ram,
+    /// The only input is the address.
+    Rom,
+}
+
+/// A RAM or ROM whose output gives the word at its address input. A RAM
+/// stores its data input on each rising edge of its clock while its write
+/// enable is 1.
+#[derive(Clone, Debug)]
+pub struct Memory {
+    pub kind: MemoryKind,
+    pub address_width: u8,
+    pub data_width: u8,
+    pub contents: Vec<u64>,
+    last_clock: Level,
+}
+
+impl Memory {
+    pub fn new(kind: MemoryKind, address_width: u8, data_width: u8) -> Self {
+        Memory {
+            kind,
+            address_width,
+            data_width,
+            contents: vec![0; 1 << address_width],
+            last_clock: Level::Low,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self.kind {
+            MemoryKind::Ram => "RAM",
+            MemoryKind::Rom => "ROM",
+        }
+    }
+
+    pub fn input_names(&self) -> &'static [&'static str] {
+        match self.kind {
+            MemoryKind::Ram => &["A", "D", "WE", "CLK"],
+            MemoryKind::Rom => &["A"],
+        }
+    }
+
+    /// The word at the address on the first input.
+    pub fn read(&self, inputs: &[Signal]) -> Signal {
+        let word = inputs
+            .first()
+            .and_then(Signal::value)
+            .and_then(|address| self.contents.get(address as usize));
+
+        match word {
+            Some(word) => Signal::new(self.data_width, *word),
+            None => Signal::undefined(self.data_width),
+        }
+    }
+
+    /// Stores the data input of a RAM on a rising edge of its clock while
+    /// its write enable is 1, returning whether a word was written.
+    pub fn clock(&mut self, inputs: &[Signal]) -> bool {
+        let (MemoryKind::Ram, [address, data, write_enable, clock]) = (self.kind, inputs) else {
+            return false;
+        };
+        let clock = clock.level(0);
+        let rising = self.last_clock == Level::Low && clock == Level::High;
+        self.last_clock = clock;
+
+        if !rising || write_enable.level(0) != Level::High || data.width() != self.data_width {
+            return false;
+        }
+        let (Some(address), Some(word)) = (address.value(), data.value()) else {
+            return false;
+        };
+        self.write(address as usize, word);
+        true
+    }
+
+    /// Stores `word` at `address`, dropping the bits wider than a word.
+    pub fn write(&mut self, address: usize, word: u64) {
+        if let Some(cell) = self.contents.get_mut(address) {
+            *cell = Signal::new(self.data_width, word).value().unwrap_or(0);
+        }
+    }
+
+    /// Replaces the contents from address 0, clearing the words past the
+    /// end of `words`. Returns whether `words` had to be truncated.
+    pub fn load(&mut self, words: &[u64]) -> bool {
+        let len = self.contents.len();
+        for address in 0..len {
+            self.write(address, words.get(address).copied().unwrap_or(0));
+        }
+        words.len() > len
+    }
+
+    /// Bytes used by each word in the binary file format.
+    fn word_bytes(&self) -> usize {
+        self.data_width.div_ceil(8) as usize
+    }
+
+    /// Reads words stored as little-endian groups of bytes.
+    pub fn words_from_binary(&self, bytes: &[u8]) -> Vec<u64> {
+        bytes
+            .chunks(self.word_bytes())
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .rev()
+                    .fold(0, |word, byte| (word << 8) | *byte as u64)
+            })
+            .collect()
+    }
+
+    pub fn to_binary(&self) -> Vec<u8> {
+        self.contents
+            .iter()
+            .flat_map(|word| word.to_le_bytes().into_iter().take(self.word_bytes()))
+            .collect()
+    }
+
+    /// Writes the contents in Logisim's "v2.0 raw" hexadecimal format.
+    pub fn to_hex(&self) -> String {
+        let mut text = String::from("v2.0 raw\n");
+        for line in self.contents.chunks(8) {
+            let words = line.iter().map(|word| format!("{word:x}"));
+            text.push_str(&words.collect::<Vec<_>>().join(" "));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// Reads words written in hexadecimal, separated by blanks. Like in
+/// Logisim's "v2.0 raw" format, `N*word` repeats a word `N` times, and the
+/// header line and `#` comments are skipped. Fails when there are more than
+/// `limit` words, the size of the memory they go to.
+pub fn words_from_hex(text: &str, limit: usize) -> Result<Vec<u64>, String> {
+    let mut words = Vec::new();
+    for line in text.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.starts_with("v2.0") {
+            continue;
+        }
+
+        for token in line.split_whitespace() {
+            let (count, word) = match token.split_once('*') {
+                Some((count, word)) => (
+                    count
+                        .parse::<usize>()
+                        .map_err(|_| format!("invalid repeat count `{count}`"))?,
+                    word,
+                ),
+                None => (1, token),
+            };
+            let word = u64::from_str_radix(word.trim_start_matches("0x"), 16)
+                .map_err(|_| format!("invalid hexadecimal word `{word}`"))?;
+            if count > limit - words.len() {
+                return Err(format!("more than {limit} words"));
+            }
+            words.extend(std::iter::repeat_n(word, count));
+        }
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words_from_hex_reads_runs_and_skips_comments() {
+        let text = "v2.0 raw\n1 0x2f # comment\n3*a\n";
+        assert_eq!(words_from_hex(text, 8), Ok(vec![1, 0x2f, 0xa, 0xa, 0xa]));
+        assert!(words_from_hex("12 zz", 8).is_err());
+        assert!(words_from_hex("x*1", 8).is_err());
+    }
+
+    #[test]
+    fn words_from_hex_rejects_more_words_than_the_memory_holds() {
+        assert_eq!(words_from_hex("4*0", 4), Ok(vec![0; 4]));
+        assert!(words_from_hex("99999999999*0", 1 << MAX_ADDRESS_WIDTH).is_err());
+        assert!(words_from_hex("1 2 3*0", 4).is_err());
+    }
+
+    #[test]
+    fn binary_words_round_trip() {
+        let mut memory = Memory::new(MemoryKind::Rom, 2, 12);
+        assert!(!memory.load(&[0x123, 0xfff, 0x1000]));
+        assert_eq!(memory.contents, vec![0x123, 0xfff, 0, 0]);
+        assert_eq!(
+            memory.words_from_binary(&memory.to_binary()),
+            memory.contents
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use crate::signal::{Level, Signal};
+
+/// Most data lines a multiplexer, demultiplexer, encoder or decoder handles.
+pub const MAX_LINES: usize = 16;
+
+/// Components routing signals according to a binary select input. Data
+/// inputs come first, the select input last.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Plexer {
+    /// Forwards the data input picked by the select input.
+    Mux,
+    /// Forwards its input to the output picked by the select input, the
+    /// other outputs being 0.
+    Demux { outputs: usize },
+    /// Gives the index of its highest input at 1 on its first output, and
+    /// whether any input is at 1 on the second.
+    Encoder { lines: usize },
+    /// Sets the output picked by the select input, clearing the others.
+    Decoder { outputs: usize },
+}
+
+/// Bits needed to select one of `lines` lines.
+pub fn select_width(lines: usize) -> u8 {
+    (usize::BITS - lines.saturating_sub(1).leading_zeros()).max(1) as u8
+}
+
+impl Plexer {
+    pub fn evaluate(&self, inputs: &[Signal]) -> Vec<Signal> {
+        match self {
+            Plexer::Mux => {
+                let Some((select, data)) = inputs.split_last() else {
+                    return vec![Signal::undefined(1)];
+                };
+                let width = data.first().map_or(1, Signal::width);
+                let picked = select
+                    .value()
+                    .and_then(|index| data.get(index as usize))
+                    .copied();
+                vec![picked.unwrap_or(Signal::undefined(width))]
+            }
+            Plexer::Demux { outputs } => {
+                let [data, select] = inputs else {
+                    return vec![];
+                };
+                (0..*outputs as u64)
+                    .map(|line| match select.value() {
+                        Some(index) if index == line => *data,
+                        Some(_) => Signal::new(data.width(), 0),
+                        None => Signal::undefined(data.width()),
+                    })
+                    .collect()
+            }
+            Plexer::Encoder { lines } => {
+                let width = select_width(*lines);
+                let mut outputs = vec![Signal::new(width, 0), Signal::bit(false)];
+                for (index, input) in inputs.iter().enumerate().rev() {
+                    match input.level(0) {
+                        Level::Low => continue,
+                        Level::High => {
+                            outputs = vec![Signal::new(width, index as u64), Signal::bit(true)];
+                        }
+                        Level::Undefined | Level::HighZ => {
+                            outputs = vec![Signal::undefined(width), Signal::undefined(1)];
+                        }
+                    }
+                    break;
+                }
+                outputs
+            }
+            Plexer::Decoder { outputs } => {
+                let select = inputs.first().and_then(Signal::value);
+                (0..*outputs as u64)
+                    .map(|line| match select {
+                        Some(index) => Signal::bit(index == line),
+                        None => Signal::undefined(1),
+                    })
+                    .collect()
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crate::signal::{Level, Signal};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RegisterKind {
+    /// Loads its data input. Inputs are D, LD and CLK.
+    Parallel,
+    /// Counts up, or down while its UP input is 0. Inputs are EN, UP, RST and
+    /// CLK.
+    Counter,
+    /// Shifts its serial input in at bit 0 and outputs its highest bit.
+    /// Inputs are SI, EN and CLK.
+    Shift,
+}
+
+/// A `width` bits register updated on the rising edges of its clock input.
+/// Unplugged enables count as 1, and the reset of a counter clears it at
+/// once, without waiting for the clock.
+#[derive(Clone, Debug)]
+pub struct Register {
+    pub kind: RegisterKind,
+    pub width: u8,
+    pub value: Signal,
+    last_clock: Level,
+}
+
+impl Register {
+    pub fn new(kind: RegisterKind, width: u8) -> Self {
+        Register {
+            kind,
+            width,
+            value: Signal::new(width, 0),
+            last_clock: Level::Low,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self.kind {
+            RegisterKind::Parallel => "REG",
+            RegisterKind::Counter => "CTR",
+            RegisterKind::Shift => "SHR",
+        }
+    }
+
+    pub fn input_names(&self) -> &'static [&'static str] {
+        match self.kind {
+            RegisterKind::Parallel => &["D", "LD", "CLK"],
+            RegisterKind::Counter => &["EN", "UP", "RST", "CLK"],
+            RegisterKind::Shift => &["SI", "EN", "CLK"],
+        }
+    }
+
+    pub fn output_names(&self) -> &'static [&'static str] {
+        match self.kind {
+            RegisterKind::Parallel | RegisterKind::Counter => &["Q"],
+            RegisterKind::Shift => &["Q", "SO"],
+        }
+    }
+
+    /// The value, followed for a shift register by its highest bit.
+    pub fn outputs(&self) -> Vec<Signal> {
+        match self.kind {
+            RegisterKind::Parallel | RegisterKind::Counter => vec![self.value],
+            RegisterKind::Shift => vec![self.value, self.value.slice(self.width - 1, 1)],
+        }
+    }
+
+    /// Updates the value on a rising edge of the clock, the last input, or
+    /// clears a counter being reset. Returns whether the value changed.
+    pub fn clock(&mut self, inputs: &[Signal]) -> bool {
+        let Some((clock, inputs)) = inputs.split_last() else {
+            return false;
+        };
+        if inputs.len() + 1 != self.input_names().len() {
+            return false;
+        }
+
+        let clock = clock.level(0);
+        let rising = self.last_clock == Level::Low && clock == Level::High;
+        self.last_clock = clock;
+
+        let reset = match self.kind {
+            RegisterKind::Counter => inputs[2].level(0),
+            RegisterKind::Parallel | RegisterKind::Shift => Level::Low,
+        };
+        let next = match reset {
+            Level::High => Signal::new(self.width, 0),
+            Level::Undefined => Signal::undefined(self.width),
+            Level::Low | Level::HighZ if rising => self.next(inputs),
+            Level::Low | Level::HighZ => return false,
+        };
+
+        let changed = next != self.value;
+        self.value = next;
+        changed
+    }
+
+    /// The value after a rising clock edge, given the other inputs.
+    fn next(&self, inputs: &[Signal]) -> Signal {
+        let enabled = |index: usize| match inputs[index].level(0) {
+            Level::High | Level::HighZ => Some(true),
+            Level::Low => Some(false),
+            Level::Undefined => None,
+        };
+
+        match self.kind {
+            RegisterKind::Parallel => match enabled(1) {
+                Some(true) if inputs[0].width() == self.width => inputs[0],
+                Some(true) | None => Signal::undefined(self.width),
+                Some(false) => self.value,
+            },
+            RegisterKind::Counter => {
+                let (Some(enable), Some(up), Some(value)) =
+                    (enabled(0), enabled(1), self.value.value())
+                else {
+                    return Signal::undefined(self.width);
+                };
+                match (enable, up) {
+                    (false, _) => self.value,
+                    (true, true) => Signal::new(self.width, value.wrapping_add(1)),
+                    (true, false) => Signal::new(self.width, value.wrapping_sub(1)),
+                }
+            }
+            RegisterKind::Shift => match enabled(1) {
+                Some(true) if self.width == 1 => inputs[0].slice(0, 1),
+                Some(true) => inputs[0]
+                    .slice(0, 1)
+                    .concat(self.value.slice(0, self.width - 1))
+                    .unwrap_or(Signal::undefined(self.width)),
+                Some(false) => self.value,
+                None => Signal::undefined(self.width),
+            },
+        }
+    }
+}
@@ -0,0 +1,321 @@
+use std::{
+    fmt,
+    ops::{BitAnd, BitOr, Not},
+};
+
+/// Widest bus a wire can carry.
+pub const MAX_WIDTH: u8 = 64;
+
+/// The state of a single bit of a signal.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Level {
+    Low,
+    High,
+    /// Driven, but to an unknown value.
+    Undefined,
+    /// Not driven at all.
+    HighZ,
+}
+
+/// The value carried by a wire: a bus of 1 to 64 bits, bit 0 being the
+/// least significant one. Each bit is 0, 1, undefined (X) or floating (Z).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Signal {
+    width: u8,
+    /// The bits known to be 1.
+    bits: u64,
+    /// The bits driven to an unknown value.
+    undefined: u64,
+    /// The bits nothing drives.
+    floating: u64,
+}
+
+impl Default for Signal {
+    fn default() -> Self {
+        Signal::bit(false)
+    }
+}
+
+impl Signal {
+    /// Builds a `width` bits signal, dropping the bits of `bits` that do not fit.
+    pub fn new(width: u8, bits: u64) -> Self {
+        Signal::from_masks(width, bits, 0, 0)
+    }
+
+    pub fn bit(state: bool) -> Self {
+        Signal::new(1, state as u64)
+    }
+
+    /// A signal whose bits are all undefined.
+    pub fn undefined(width: u8) -> Self {
+        Signal::from_masks(width, 0, u64::MAX, 0)
+    }
+
+    /// A signal whose bits are all floating.
+    pub fn floating(width: u8) -> Self {
+        Signal::from_masks(width, 0, 0, u64::MAX)
+    }
+
+    fn from_masks(width: u8, bits: u64, undefined: u64, floating: u64) -> Self {
+        let width = width.clamp(1, MAX_WIDTH);
+        let mask = Self::mask(width);
+        let floating = floating & mask;
+        let undefined = undefined & mask & !floating;
+        Signal {
+            width,
+            bits: bits & mask & !(undefined | floating),
+            undefined,
+            floating,
+        }
+    }
+
+    fn mask(width: u8) -> u64 {
+        if width >= MAX_WIDTH {
+            u64::MAX
+        } else {
+            (1 << width) - 1
+        }
+    }
+
+    pub fn width(&self) -> u8 {
+        self.width
+    }
+
+    pub fn level(&self, index: u8) -> Level {
+        if index >= self.width {
+            return Level::HighZ;
+        }
+
+        let bit = 1 << index;
+        if self.floating & bit != 0 {
+            Level::HighZ
+        } else if self.undefined & bit != 0 {
+            Level::Undefined
+        } else if self.bits & bit != 0 {
+            Level::High
+        } else {
+            Level::Low
+        }
+    }
+
+    /// The bits as a number, unless some of them are undefined or floating.
+    pub fn value(&self) -> Option<u64> {
+        (self.undefined | self.floating == 0).then_some(self.bits)
+    }
+
+    /// Whether any bit is known to be 1.
+    pub fn is_high(&self) -> bool {
+        self.bits != 0
+    }
+
+    /// Whether any bit is undefined.
+    pub fn is_undefined(&self) -> bool {
+        self.undefined != 0
+    }
+
+    /// Whether any bit is floating.
+    pub fn is_floating(&self) -> bool {
+        self.floating != 0
+    }
+
+    /// The bits known to be 0.
+    fn zeros(&self) -> u64 {
+        Self::mask(self.width) & !(self.bits | self.undefined | self.floating)
+    }
+
+    /// Extracts `width` bits starting at bit `low`, the bits past the end of
+    /// the bus being floating.
+    pub fn slice(&self, low: u8, width: u8) -> Signal {
+        let shift = |mask: u64| mask.checked_shr(low as u32).unwrap_or(0);
+        let past_end = !Self::mask(self.width.saturating_sub(low));
+        Signal::from_masks(
+            width,
+            shift(self.bits),
+            shift(self.undefined),
+            shift(self.floating) | past_end,
+        )
+    }
+
+    /// Concatenates `high` above the bits of `self`.
+    pub fn concat(&self, high: Signal) -> Option<Signal> {
+        let width = self.width.checked_add(high.width)?;
+        (width <= MAX_WIDTH).then(|| {
+            Signal::from_masks(
+                width,
+                self.bits | (high.bits << self.width),
+                self.undefined | (high.undefined << self.width),
+                self.floating | (high.floating << self.width),
+            )
+        })
+    }
+
+    /// The value of a wire driven by both `self` and `other`: a floating bit
+    /// takes the value of the other driver, disagreeing drivers give an
+    /// undefined bit.
+    pub fn resolve(self, other: Signal) -> Signal {
+        if self.width != other.width {
+            return Signal::undefined(self.width.max(other.width));
+        }
+
+        let floating = self.floating & other.floating;
+        let ones = self.bits | other.bits;
+        let zeros = self.zeros() | other.zeros();
+        let undefined = self.undefined | other.undefined | (ones & zeros);
+        Signal::from_masks(self.width, ones, undefined, floating)
+    }
+
+    /// Gives the floating bits the value a pull resistor forces them to, an
+    /// undefined level standing for resistors pulling both ways.
+    pub fn pull(self, level: Level) -> Signal {
+        let (ones, undefined) = match level {
+            Level::High => (self.floating, 0),
+            Level::Low => (0, 0),
+            Level::Undefined => (0, self.floating),
+            Level::HighZ => return self,
+        };
+        Signal::from_masks(self.width, self.bits | ones, self.undefined | undefined, 0)
+    }
+
+    /// Whether two drivers of the same wire force some bit to opposite values.
+    pub fn conflicts(&self, other: &Signal) -> bool {
+        self.width != other.width
+            || (self.bits & other.zeros()) != 0
+            || (self.zeros() & other.bits) != 0
+    }
+}
+
+/// Writes a known signal in hexadecimal, and any other one bit by bit from
+/// the highest, as `0`, `1`, `x` or `z`.
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(value) = self.value()
+            && self.width > 1
+        {
+            let digits = self.width.div_ceil(4) as usize;
+            return write!(f, "{value:0digits$x}");
+        }
+
+        for index in (0..self.width).rev() {
+            let digit = match self.level(index) {
+                Level::Low => '0',
+                Level::High => '1',
+                Level::Undefined => 'x',
+                Level::HighZ => 'z',
+            };
+            write!(f, "{digit}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Not for Signal {
+    type Output = Signal;
+
+    fn not(self) -> Signal {
+        Signal::from_masks(self.width, self.zeros(), self.undefined | self.floating, 0)
+    }
+}
+
+/// Bitwise AND: a 0 on either side wins, otherwise an unknown bit gives X.
+impl BitAnd for Signal {
+    type Output = Signal;
+
+    fn bitand(self, other: Signal) -> Signal {
+        if self.width != other.width {
+            return Signal::undefined(self.width.max(other.width));
+        }
+
+        let zeros = self.zeros() | other.zeros();
+        let ones = self.bits & other.bits;
+        Signal::from_masks(self.width, ones, !(zeros | ones), 0)
+    }
+}
+
+/// Bitwise OR: a 1 on either side wins, otherwise an unknown bit gives X.
+impl BitOr for Signal {
+    type Output = Signal;
+
+    fn bitor(self, other: Signal) -> Signal {
+        if self.width != other.width {
+            return Signal::undefined(self.width.max(other.width));
+        }
+
+        let ones = self.bits | other.bits;
+        let zeros = self.zeros() & other.zeros();
+        Signal::from_masks(self.width, ones, !(zeros | ones), 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_lets_driven_bits_win_over_floating_ones() {
+        assert_eq!(
+            Signal::bit(true).resolve(Signal::floating(1)),
+            Signal::bit(true)
+        );
+        assert_eq!(
+            Signal::floating(1).resolve(Signal::bit(false)),
+            Signal::bit(false)
+        );
+        assert_eq!(
+            Signal::floating(1).resolve(Signal::floating(1)),
+            Signal::floating(1)
+        );
+    }
+
+    #[test]
+    fn resolve_makes_opposite_bits_undefined() {
+        let resolved = Signal::new(2, 0b01).resolve(Signal::new(2, 0b11));
+        assert_eq!(resolved.level(0), Level::High);
+        assert_eq!(resolved.level(1), Level::Undefined);
+        assert_eq!(
+            Signal::bit(true).resolve(Signal::new(4, 1)),
+            Signal::undefined(4)
+        );
+    }
+
+    #[test]
+    fn pull_only_sets_floating_bits() {
+        let half = Signal::bit(false).concat(Signal::floating(1)).unwrap();
+        assert_eq!(half.pull(Level::High), Signal::new(2, 0b10));
+        assert_eq!(half.pull(Level::Low), Signal::new(2, 0b00));
+        assert_eq!(half.pull(Level::Undefined).to_string(), "x0");
+        assert_eq!(half.pull(Level::HighZ), half);
+        assert_eq!(Signal::bit(true).pull(Level::Low), Signal::bit(true));
+    }
+
+    #[test]
+    fn conflicts_only_between_opposite_driven_bits() {
+        assert!(Signal::bit(true).conflicts(&Signal::bit(false)));
+        assert!(!Signal::bit(true).conflicts(&Signal::bit(true)));
+        assert!(!Signal::bit(true).conflicts(&Signal::floating(1)));
+        assert!(!Signal::bit(false).conflicts(&Signal::undefined(1)));
+        assert!(Signal::bit(true).conflicts(&Signal::new(2, 1)));
+    }
+
+    #[test]
+    fn slice_floats_the_bits_past_the_end() {
+        assert_eq!(Signal::new(8, 0xa5).slice(4, 4), Signal::new(4, 0xa));
+        assert_eq!(Signal::new(4, 0xf).slice(2, 4).to_string(), "zz11");
+        assert_eq!(Signal::new(4, 0xf).slice(8, 1), Signal::floating(1));
+    }
+
+    #[test]
+    fn concat_puts_the_other_signal_above() {
+        assert_eq!(
+            Signal::new(4, 0x5).concat(Signal::new(4, 0xa)),
+            Some(Signal::new(8, 0xa5))
+        );
+        assert_eq!(
+            Signal::bit(true)
+                .concat(Signal::floating(1))
+                .unwrap()
+                .to_string(),
+            "z1"
+        );
+        assert_eq!(Signal::new(MAX_WIDTH, 0).concat(Signal::bit(true)), None);
+    }
+}
@@ -0,0 +1,468 @@
+use std::{collections::HashSet, hash::Hash};
+
+use crate::{
+    element::{Element, Operation},
+    signal::{Level, Signal},
+};
+
+/// Passes over a circuit after which an evaluation is considered not to
+/// settle, e.g. because of a ring oscillator.
+pub const MAX_PASSES: usize = 256;
+
+/// Refers to a part of a circuit, by the order it was added in.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct PartId(pub usize);
+
+/// A part of a circuit. Inputs refer to the parts they are plugged into,
+/// unplugged ones being floating.
+#[derive(Clone, Debug)]
+pub enum Part {
+    /// A value set from outside the circuit, like a button.
+    Input(Signal),
+    Constant(Signal),
+    /// Pulls the net it belongs to to a level, where nothing drives it.
+    Pull(Level),
+    Component(Element, Vec<Option<PartId>>),
+    /// Output `index` of a component with several outputs.
+    Pin(PartId, usize),
+    /// A wire node splitting one signal into several branches. It forwards
+    /// the value of its input unchanged, so consumers read straight through
+    /// it.
+    Junction(Option<PartId>),
+    /// A named wire end. All tunnels sharing a name form one net, so the
+    /// signal driven into one of them is available at every other.
+    Tunnel(String, Option<PartId>),
+}
+
+/// A circuit made of parts, built without any window or rendering.
+#[derive(Clone, Default, Debug)]
+pub struct Circuit {
+    parts: Vec<Part>,
+}
+
+impl Circuit {
+    pub fn add(&mut self, part: Part) -> PartId {
+        self.parts.push(part);
+        PartId(self.parts.len() - 1)
+    }
+
+    pub fn gate(&mut self, operation: Operation, inputs: &[PartId]) -> PartId {
+        let mut inputs = inputs.iter().copied().map(Some).collect::<Vec<_>>();
+        inputs.resize(operation.inputs(), None);
+        self.add(Part::Component(Element::Gate(operation), inputs))
+    }
+
+    pub fn part(&self, id: PartId) -> Option<&Part> {
+        self.parts.get(id.0)
+    }
+
+    /// Plugs `source` into input `index` of `part`.
+    pub fn connect(&mut self, part: PartId, index: usize, source: PartId) -> Result<(), String> {
+        if source.0 >= self.parts.len() {
+            return Err(format!("no part {}", source.0));
+        }
+        let input = match self.parts.get_mut(part.0) {
+            Some(Part::Component(_, inputs)) => inputs.get_mut(index),
+            Some(Part::Junction(input) | Part::Tunnel(_, input)) if index == 0 => Some(input),
+            Some(_) => None,
+            None => return Err(format!("no part {}", part.0)),
+        };
+        let input = input.ok_or_else(|| format!("part {} has no input {index}", part.0))?;
+        *input = Some(source);
+        Ok(())
+    }
+}
+
+impl Wiring for Circuit {
+    type Id = PartId;
+
+    fn node(&self, id: PartId) -> Node<'_, PartId> {
+        match self.part(id) {
+            Some(Part::Junction(input)) => Node::Junction(*input),
+            Some(Part::Tunnel(name, _)) => Node::Tunnel(name),
+            Some(Part::Pull(level)) => Node::Pull(*level),
+            Some(_) => Node::Driver,
+            None => Node::Junction(None),
+        }
+    }
+
+    fn tunnel_inputs(&self, name: &str) -> Vec<PartId> {
+        self.parts
+            .iter()
+            .filter_map(|part| match part {
+                Part::Tunnel(other, input) if other == name => *input,
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// What a part of a circuit is to the net it belongs to.
+pub enum Node<'a, Id> {
+    /// Carries the net of its input, if plugged.
+    Junction(Option<Id>),
+    /// Joins the nets of all the tunnels with this name.
+    Tunnel(&'a str),
+    /// Pulls the net to a level, where nothing drives it.
+    Pull(Level),
+    /// Drives the net with its output.
+    Driver,
+}
+
+/// The wiring of a circuit, through which the drivers of a net are found.
+/// Both the headless circuits and the editor implement it.
+pub trait Wiring {
+    type Id: Copy + Eq + Hash;
+
+    fn node(&self, id: Self::Id) -> Node<'_, Self::Id>;
+
+    /// The inputs of the tunnels named `name`.
+    fn tunnel_inputs(&self, name: &str) -> Vec<Self::Id>;
+
+    /// Follows junctions and tunnels back to the parts actually driving the
+    /// net `id` belongs to, pulls included.
+    fn drivers(&self, id: Self::Id) -> Vec<Self::Id> {
+        let mut visited = HashSet::new();
+        let mut drivers = Vec::new();
+        collect_drivers(self, id, &mut visited, &mut drivers);
+        drivers
+    }
+
+    /// The parts driving the net `id` belongs to, apart from the pulls, and
+    /// the level these pull the net to.
+    fn pulled_drivers(&self, id: Self::Id) -> (Vec<Self::Id>, Level) {
+        let mut pull = Level::HighZ;
+        let mut drivers = self.drivers(id);
+        drivers.retain(|driver| {
+            let Node::Pull(level) = self.node(*driver) else {
+                return true;
+            };
+            pull = match pull {
+                Level::HighZ => level,
+                other if other == level => other,
+                _ => Level::Undefined,
+            };
+            false
+        });
+        (drivers, pull)
+    }
+}
+
+fn collect_drivers<W: Wiring + ?Sized>(
+    wiring: &W,
+    id: W::Id,
+    visited: &mut HashSet<W::Id>,
+    drivers: &mut Vec<W::Id>,
+) {
+    if !visited.insert(id) {
+        return;
+    }
+
+    match wiring.node(id) {
+        Node::Junction(Some(input)) => collect_drivers(wiring, input, visited, drivers),
+        Node::Junction(None) => {}
+        Node::Tunnel(name) => {
+            for input in wiring.tunnel_inputs(name) {
+                collect_drivers(wiring, input, visited, drivers);
+            }
+        }
+        Node::Pull(_) | Node::Driver => drivers.push(id),
+    }
+}
+
+/// Combines the signals of the drivers of a net, pull resistors only setting
+/// the bits no driver sets. Also tells whether drivers force some bit to
+/// opposite values.
+pub fn resolve_net(signals: Vec<Signal>, pull: Level) -> (Signal, bool) {
+    let contention = signals
+        .iter()
+        .enumerate()
+        .any(|(i, a)| signals[i + 1..].iter().any(|b| a.conflicts(b)));
+    let state = signals
+        .into_iter()
+        .reduce(Signal::resolve)
+        .unwrap_or(Signal::floating(1));
+    (state.pull(pull), contention)
+}
+
+/// Evaluates a circuit tick after tick, each tick moving signals one gate
+/// further as in the editor.
+pub struct Simulator {
+    circuit: Circuit,
+    /// The drivers and pull of the net of each part.
+    nets: Vec<(Vec<PartId>, Level)>,
+    /// The output of each part.
+    values: Vec<Signal>,
+    pub ticks: u64,
+}
+
+impl Simulator {
+    pub fn new(circuit: Circuit) -> Self {
+        let nets = (0..circuit.parts.len())
+            .map(|index| circuit.pulled_drivers(PartId(index)))
+            .collect();
+        let values = circuit
+            .parts
+            .iter()
+            .map(|part| match part {
+                Part::Input(signal) | Part::Constant(signal) => *signal,
+                _ => Signal::default(),
+            })
+            .collect();
+        Simulator {
+            circuit,
+            nets,
+            values,
+            ticks: 0,
+        }
+    }
+
+    pub fn circuit(&self) -> &Circuit {
+        &self.circuit
+    }
+
+    /// Sets the value of an input.
+    pub fn set(&mut self, input: PartId, signal: Signal) -> Result<(), String> {
+        match (self.circuit.part(input), self.values.get_mut(input.0)) {
+            (Some(Part::Input(_)), Some(value)) => {
+                *value = signal;
+                Ok(())
+            }
+            _ => Err(format!("part {} is not an input", input.0)),
+        }
+    }
+
+    /// The output of every part, to be put back with `restore`.
+    pub fn values(&self) -> &[Signal] {
+        &self.values
+    }
+
+    /// Puts back the outputs of the parts, as taken by `values`.
+    pub fn restore(&mut self, values: &[Signal]) {
+        for (value, restored) in self.values.iter_mut().zip(values) {
+            *value = *restored;
+        }
+    }
+
+    /// The value of the net `id` belongs to, and whether its drivers force
+    /// some bit to opposite values.
+    pub fn resolve(&self, id: PartId) -> (Signal, bool) {
+        let Some((drivers, pull)) = self.nets.get(id.0) else {
+            return (Signal::undefined(1), false);
+        };
+        let signals = drivers
+            .iter()
+            .map(|driver| self.values.get(driver.0).copied().unwrap_or_default());
+        resolve_net(signals.collect(), *pull)
+    }
+
+    /// The value of the net `id` belongs to.
+    pub fn value(&self, id: PartId) -> Signal {
+        self.resolve(id).0
+    }
+
+    /// The signals on the given inputs, unplugged ones being floating.
+    fn inputs(&self, inputs: &[Option<PartId>]) -> Vec<Signal> {
+        inputs
+            .iter()
+            .map(|input| match input {
+                Some(input) => self.value(*input),
+                None => Signal::floating(1),
+            })
+            .collect()
+    }
+
+    /// Clocks the registers and memories, then computes every component from
+    /// the values of the previous tick, returning whether some output or
+    /// contents changed. A component gives its first output, and its pins
+    /// the others.
+    pub fn tick(&mut self) -> bool {
+        let clocked = self
+            .circuit
+            .parts
+            .iter()
+            .enumerate()
+            .filter_map(|(index, part)| match part {
+                Part::Component(Element::Register(_) | Element::Memory(_), inputs) => {
+                    Some((index, self.inputs(inputs)))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let mut changed = false;
+        for (index, inputs) in clocked {
+            if let Some(Part::Component(element, _)) = self.circuit.parts.get_mut(index) {
+                changed |= element.clock(&inputs);
+            }
+        }
+
+        let outputs = self
+            .circuit
+            .parts
+            .iter()
+            .map(|part| match part {
+                Part::Component(element, inputs) => element.evaluate(&self.inputs(inputs)),
+                _ => Vec::new(),
+            })
+            .collect::<Vec<_>>();
+
+        self.ticks += 1;
+        for (index, part) in self.circuit.parts.iter().enumerate() {
+            let output = match part {
+                Part::Component(..) => outputs[index].first(),
+                Part::Pin(component, pin) => outputs
+                    .get(component.0)
+                    .and_then(|outputs| outputs.get(*pin)),
+                _ => continue,
+            };
+            let output = output.copied().unwrap_or(Signal::undefined(1));
+            changed |= self.values[index] != output;
+            self.values[index] = output;
+        }
+        changed
+    }
+
+    /// Ticks until the values stop changing, returning whether they did
+    /// within `MAX_PASSES` ticks.
+    pub fn settle(&mut self) -> bool {
+        (0..MAX_PASSES).any(|_| !self.tick())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sequential::{Register, RegisterKind};
+
+    #[test]
+    fn resolve_net_combines_drivers_then_pulls() {
+        let signals = vec![Signal::bit(true), Signal::floating(1)];
+        assert_eq!(resolve_net(signals, Level::Low), (Signal::bit(true), false));
+
+        let signals = vec![Signal::bit(true), Signal::bit(false)];
+        assert_eq!(
+            resolve_net(signals, Level::HighZ),
+            (Signal::undefined(1), true)
+        );
+
+        assert_eq!(resolve_net(vec![], Level::High), (Signal::bit(true), false));
+        assert_eq!(
+            resolve_net(vec![], Level::HighZ),
+            (Signal::floating(1), false)
+        );
+    }
+
+    #[test]
+    fn drivers_follow_junctions_and_tunnels_to_sources_and_pulls() {
+        let mut circuit = Circuit::default();
+        let input = circuit.add(Part::Input(Signal::bit(true)));
+        circuit.add(Part::Tunnel("x".to_string(), Some(input)));
+        let up = circuit.add(Part::Pull(Level::High));
+        let tunnel = circuit.add(Part::Tunnel("x".to_string(), Some(up)));
+        let junction = circuit.add(Part::Junction(Some(tunnel)));
+        circuit.add(Part::Tunnel("y".to_string(), None));
+
+        assert_eq!(circuit.drivers(junction), vec![input, up]);
+        assert_eq!(circuit.pulled_drivers(junction), (vec![input], Level::High));
+
+        let down = circuit.add(Part::Pull(Level::Low));
+        circuit.add(Part::Tunnel("x".to_string(), Some(down)));
+        assert_eq!(
+            circuit.pulled_drivers(junction),
+            (vec![input], Level::Undefined)
+        );
+    }
+
+    #[test]
+    fn tick_moves_signals_one_gate_further() {
+        let mut circuit = Circuit::default();
+        let input = circuit.add(Part::Input(Signal::bit(false)));
+        let first = circuit.gate(Operation::Not, &[input]);
+        let second = circuit.gate(Operation::Not, &[first]);
+        let mut simulator = Simulator::new(circuit);
+
+        assert!(simulator.tick());
+        assert_eq!(simulator.value(second), Signal::bit(true));
+        assert!(simulator.tick());
+        assert_eq!(simulator.value(second), Signal::bit(false));
+        assert!(!simulator.tick());
+        assert_eq!(simulator.ticks, 3);
+
+        simulator.set(input, Signal::bit(true)).unwrap();
+        assert!(simulator.settle());
+        assert_eq!(simulator.value(first), Signal::bit(false));
+        assert_eq!(simulator.value(second), Signal::bit(true));
+    }
+
+    #[test]
+    fn settle_gives_up_on_a_ring_oscillator() {
+        let mut circuit = Circuit::default();
+        let first = circuit.gate(Operation::Not, &[]);
+        let second = circuit.gate(Operation::Not, &[first]);
+        let third = circuit.gate(Operation::Not, &[second]);
+        circuit.connect(first, 0, third).unwrap();
+        let mut simulator = Simulator::new(circuit);
+
+        assert!(!simulator.settle());
+        assert_eq!(simulator.ticks, MAX_PASSES as u64);
+    }
+
+    #[test]
+    fn tick_clocks_counters_on_rising_edges() {
+        let mut circuit = Circuit::default();
+        let clock = circuit.add(Part::Input(Signal::bit(false)));
+        let reset = circuit.add(Part::Input(Signal::bit(false)));
+        let counter = Register::new(RegisterKind::Counter, 4);
+        let counter = circuit.add(Part::Component(
+            Element::Register(counter),
+            vec![None, None, Some(reset), Some(clock)],
+        ));
+        let mut simulator = Simulator::new(circuit);
+
+        for _ in 0..3 {
+            simulator.set(clock, Signal::bit(true)).unwrap();
+            assert!(simulator.settle());
+            simulator.set(clock, Signal::bit(false)).unwrap();
+            assert!(simulator.settle());
+        }
+        assert_eq!(simulator.value(counter), Signal::new(4, 3));
+
+        simulator.set(reset, Signal::bit(true)).unwrap();
+        assert!(simulator.settle());
+        assert_eq!(simulator.value(counter), Signal::new(4, 0));
+    }
+
+    #[test]
+    fn pins_give_the_other_outputs_of_a_component() {
+        let mut circuit = Circuit::default();
+        let bus = circuit.add(Part::Input(Signal::new(2, 0b10)));
+        let splitter = circuit.add(Part::Component(Element::Splitter(2), vec![Some(bus)]));
+        let low = circuit.add(Part::Pin(splitter, 0));
+        let high = circuit.add(Part::Pin(splitter, 1));
+        let missing = circuit.add(Part::Pin(splitter, 2));
+        let mut simulator = Simulator::new(circuit);
+
+        assert!(simulator.settle());
+        assert_eq!(simulator.value(low), Signal::bit(false));
+        assert_eq!(simulator.value(high), Signal::bit(true));
+        assert_eq!(simulator.value(missing), Signal::undefined(1));
+    }
+
+    #[test]
+    fn set_only_changes_inputs() {
+        let mut circuit = Circuit::default();
+        let input = circuit.add(Part::Input(Signal::bit(false)));
+        let gate = circuit.gate(Operation::Not, &[input]);
+        let mut simulator = Simulator::new(circuit);
+
+        assert_eq!(simulator.set(input, Signal::bit(true)), Ok(()));
+        assert_eq!(
+            simulator.set(gate, Signal::bit(true)),
+            Err("part 1 is not an input".to_string())
+        );
+        assert_eq!(
+            simulator.set(PartId(7), Signal::bit(true)),
+            Err("part 7 is not an input".to_string())
+        );
+    }
+}
@@ -1,28 +1,172 @@
 use bevy::{input::keyboard::KeyboardInput, prelude::*};
 
 use crate::{
-    logic::{LogicButton, Value},
-    selection::Selected,
+    console::{Console, ConsoleAppExt, ConsoleCommand, console_closed},
+    cursor::CursorPosition,
+    logic::{ButtonKind, LogicButton, Value},
+    selection::{Selected, SelectionSystems},
+    signal::Signal,
 };
 
 pub struct ActionButtonPlugin;
 impl Plugin for ActionButtonPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, change_button_value);
+        app.add_console_command(
+            "bind",
+            "bind <key>, drive the selected buttons with a digit or F1-F12 key",
+        )
+        .add_console_command("unbind", "unbind, remove the key of the selected buttons")
+        .add_systems(
+            Update,
+            (change_button_value, press_bound_buttons).run_if(console_closed),
+        )
+        .add_systems(Update, click_button_knobs.before(SelectionSystems))
+        .add_systems(Update, bind_buttons);
     }
 }
 
+/// Flips a toggle, or sets a push-button while it is held.
+fn press(button: &LogicButton, value: &mut Value, pressed: bool) {
+    let state = match button.kind {
+        ButtonKind::Toggle if pressed => !value.state,
+        ButtonKind::Toggle => return,
+        ButtonKind::Momentary => Signal::bit(pressed),
+    };
+    *value = Value { state };
+}
+
 fn change_button_value(
-    mut button_query: Query<&mut Value, (With<LogicButton>, With<Selected>)>,
+    mut button_query: Query<(&LogicButton, &mut Value), With<Selected>>,
     mut inputs: MessageReader<KeyboardInput>,
 ) {
     for input in inputs.read() {
-        if input.key_code == KeyCode::Space && input.state.is_pressed() {
-            for mut value in button_query.iter_mut() {
-                *value = Value {
-                    state: !value.state,
-                };
+        if input.key_code == KeyCode::Space && !input.repeat {
+            for (button, mut value) in button_query.iter_mut() {
+                press(button, &mut value, input.state.is_pressed());
+            }
+        }
+    }
+}
+
+fn press_bound_buttons(
+    mut button_query: Query<(&LogicButton, &mut Value)>,
+    keys: Res<ButtonInput<KeyCode>>,
+) {
+    for (button, mut value) in button_query.iter_mut() {
+        let Some(key) = button.key else {
+            continue;
+        };
+        if keys.just_pressed(key) {
+            press(button, &mut value, true);
+        } else if keys.just_released(key) {
+            press(button, &mut value, false);
+        }
+    }
+}
+
+/// Operates the button whose knob is clicked, hiding the click from the
+/// selection so that it stays as it was.
+fn click_button_knobs(
+    mut button_query: Query<(Entity, &GlobalTransform, &LogicButton, &mut Value)>,
+    mut mouse_buttons: ResMut<ButtonInput<MouseButton>>,
+    cursor_pos: Res<CursorPosition>,
+    mut held: Local<Option<Entity>>,
+) {
+    if mouse_buttons.just_released(MouseButton::Left)
+        && let Some(entity) = held.take()
+    {
+        if let Ok((_, _, button, mut value)) = button_query.get_mut(entity) {
+            press(button, &mut value, false);
+        }
+        mouse_buttons.clear_just_released(MouseButton::Left);
+    }
+
+    if !mouse_buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let world_point = cursor_pos.in_world.extend(0.0);
+    let clicked = button_query.iter_mut().find(|(_, transform, button, _)| {
+        let local_point = transform
+            .compute_transform()
+            .to_matrix()
+            .inverse()
+            .transform_point3(world_point)
+            .truncate();
+
+        button.on_knob(local_point)
+    });
+
+    if let Some((entity, _, button, mut value)) = clicked {
+        press(button, &mut value, true);
+        *held = Some(entity);
+        mouse_buttons.clear_just_pressed(MouseButton::Left);
+    }
+}
+
+/// The keys a button may be bound to, which no shortcut uses.
+fn parse_key(name: &str) -> Option<KeyCode> {
+    const DIGITS: [KeyCode; 10] = [
+        KeyCode::Digit0,
+        KeyCode::Digit1,
+        KeyCode::Digit2,
+        KeyCode::Digit3,
+        KeyCode::Digit4,
+        KeyCode::Digit5,
+        KeyCode::Digit6,
+        KeyCode::Digit7,
+        KeyCode::Digit8,
+        KeyCode::Digit9,
+    ];
+    const FUNCTIONS: [KeyCode; 12] = [
+        KeyCode::F1,
+        KeyCode::F2,
+        KeyCode::F3,
+        KeyCode::F4,
+        KeyCode::F5,
+        KeyCode::F6,
+        KeyCode::F7,
+        KeyCode::F8,
+        KeyCode::F9,
+        KeyCode::F10,
+        KeyCode::F11,
+        KeyCode::F12,
+    ];
+
+    if let Ok(digit) = name.parse::<usize>() {
+        return DIGITS.get(digit).copied();
+    }
+    let function = name.strip_prefix(['F', 'f'])?.parse::<usize>().ok()?;
+    FUNCTIONS.get(function.checked_sub(1)?).copied()
+}
+
+fn bind_buttons(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    mut buttons: Query<&mut LogicButton, With<Selected>>,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        let key = match (command.name.as_str(), command.args().as_slice()) {
+            ("bind", [name]) => match parse_key(name) {
+                Some(key) => Some(key),
+                None => {
+                    console.print(format!("cannot bind `{name}`, use 0-9 or F1-F12"));
+                    continue;
+                }
+            },
+            ("bind", _) => {
+                console.print("usage: bind <key>");
+                continue;
             }
+            ("unbind", _) => None,
+            _ => continue,
+        };
+
+        if buttons.is_empty() {
+            console.print("no button selected");
+        }
+        for mut button in buttons.iter_mut() {
+            button.key = key;
         }
     }
 }
@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+
+use crate::{
+    console::{Console, ConsoleAppExt, ConsoleCommand},
+    logic::{
+        Scheduler, SchedulerSystems,
+        clock::{MAX_FREQUENCY, SimulationClock},
+    },
+};
+
+/// Most ticks a single `step` command runs.
+const MAX_STEPS: u64 = 1_000_000;
+
+pub struct ActionClockPlugin;
+impl Plugin for ActionClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_console_command(
+            "clockrate",
+            "clockrate <hz>, set the simulation clock frequency, 0 pausing it",
+        )
+        .add_console_command("tick", "tick, toggle the simulation clock by hand")
+        .add_console_command("pause", "pause, freeze every value of the circuit")
+        .add_console_command("resume", "resume, run the paused simulation again")
+        .add_console_command(
+            "step",
+            "step [ticks], run the paused simulation for a few ticks",
+        )
+        .add_systems(Update, clock_commands.in_set(SchedulerSystems));
+    }
+}
+
+fn clock_commands(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    mut clock: ResMut<SimulationClock>,
+    mut scheduler: ResMut<Scheduler>,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        match command.name.as_str() {
+            "clockrate" => match command.number_arg(0, 1.0, 0.0..=MAX_FREQUENCY) {
+                Ok(frequency) => clock.frequency = frequency,
+                Err(error) => console.print(error),
+            },
+            "tick" => clock.step(),
+            "pause" => scheduler.paused = true,
+            "resume" => scheduler.paused = false,
+            "step" => match command.number_arg(0, 1, 1..=MAX_STEPS) {
+                Ok(steps) => {
+                    scheduler.paused = true;
+                    scheduler.steps = steps;
+                }
+                Err(error) => console.print(error),
+            },
+            _ => {}
+        }
+    }
+}
@@ -1,85 +1,84 @@
 use bevy::{input::keyboard::KeyboardInput, prelude::*};
 
 use crate::{
+    console::console_closed,
     cursor::CursorPosition,
     link::Link,
-    logic::{Gate, update_logic_system},
-    selection::Selected,
+    logic::{
+        Gate, Inputs, Junction, LogicSystems, OutputPin, Tunnel,
+        arithmetic::Arithmetic,
+        bus::{Merger, Splitter},
+        display::SegmentDisplay,
+        memory::Memory,
+        plexer::Plexer,
+        sequential::Register,
+    },
+    selection::{Selected, SelectionSystems},
 };
 
 pub struct ActionGatePlugin;
 impl Plugin for ActionGatePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, suppr_items.after(update_logic_system))
-            .add_systems(Update, movement_item);
+        app.add_systems(
+            Update,
+            suppr_items.after(LogicSystems).run_if(console_closed),
+        )
+        .add_systems(
+            Update,
+            (
+                disconnect_links::<Gate>,
+                disconnect_links::<Junction>,
+                disconnect_links::<Tunnel>,
+                disconnect_links::<Splitter>,
+                disconnect_links::<Merger>,
+                disconnect_links::<Plexer>,
+                disconnect_links::<Arithmetic>,
+                disconnect_links::<Memory>,
+                disconnect_links::<Register>,
+                disconnect_links::<SegmentDisplay>,
+            )
+                .run_if(console_closed),
+        )
+        .add_systems(Update, movement_item.after(SelectionSystems));
     }
 }
 
 fn suppr_items(
-    query: Query<(Entity, Has<Link>), With<Selected>>,
+    query: Query<Entity, (With<Selected>, Without<OutputPin>)>,
     mut commands: Commands,
     mut inputs: MessageReader<KeyboardInput>,
-    mut all_query: Query<(Entity, &mut Gate)>,
-    link_query: Query<&Link>,
 ) {
     for input in inputs.read() {
         if input.key_code == KeyCode::Delete && input.state.is_pressed() {
-            for (entity, has_link) in query.iter() {
+            for entity in query.iter() {
                 commands.entity(entity).despawn_children();
                 commands.entity(entity).despawn();
+            }
+        }
+    }
+}
 
-                if !has_link {
-                    continue;
-                }
-
-                let link = link_query.get(entity).unwrap();
-
-                for (other_entity, mut gate) in all_query.iter_mut() {
-                    if other_entity == link.from {
-                        *gate = match &mut *gate {
-                            Gate::And(a, b) => {
-                                let mut new_a = None;
-                                let mut new_b = None;
-
-                                if *a != Some(link.to) {
-                                    new_a = a.clone();
-                                }
-                                if *b != Some(link.to) {
-                                    new_b = b.clone();
-                                }
-
-                                Gate::And(new_a, new_b)
-                            }
-                            Gate::Or(a, b) => {
-                                let mut new_a = None;
-                                let mut new_b = None;
-
-                                if *a != Some(link.to) {
-                                    new_a = a.clone();
-                                }
-                                if *b != Some(link.to) {
-                                    new_b = b.clone();
-                                }
-
-                                Gate::Or(new_a, new_b)
-                            }
-                            Gate::Not(a) => {
-                                let mut new_a = None;
-
-                                if *a != Some(link.to) {
-                                    new_a = a.clone();
-                                }
-
-                                Gate::Not(new_a)
-                            }
-                        }
-                    }
+/// Unplugs the input behind every selected link about to be deleted.
+fn disconnect_links<T: Inputs>(
+    query: Query<&Link, With<Selected>>,
+    mut inputs: MessageReader<KeyboardInput>,
+    mut all_query: Query<&mut T>,
+) {
+    for input in inputs.read() {
+        if input.key_code == KeyCode::Delete && input.state.is_pressed() {
+            for link in query.iter() {
+                if let Ok(mut component) = all_query.get_mut(link.from)
+                    && let Some(plugged) = component.inputs_mut().into_iter().nth(link.input)
+                    && *plugged == Some(link.to)
+                {
+                    *plugged = None;
                 }
             }
         }
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn movement_item(
     buttons: Res<ButtonInput<MouseButton>>,
     cursor: Res<CursorPosition>,
@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+
+use crate::{
+    console::{Console, ConsoleAppExt, ConsoleCommand},
+    logic::{Item, ItemLabel},
+    selection::Selected,
+};
+
+pub struct ActionLabelPlugin;
+impl Plugin for ActionLabelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_console_command(
+            "label",
+            "label [name], name the selected item, or clear its name",
+        )
+        .add_systems(Update, label_items);
+    }
+}
+
+fn label_items(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    items: Query<Entity, (With<Item>, With<Selected>)>,
+    mut commands: Commands,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        if command.name != "label" {
+            continue;
+        }
+
+        let mut selected = items.iter();
+        let (Some(item), None) = (selected.next(), selected.next()) else {
+            console.print("select exactly one item to label");
+            continue;
+        };
+
+        match command.args().as_slice() {
+            [] => {
+                commands.entity(item).remove::<ItemLabel>();
+            }
+            [name] => {
+                commands.entity(item).insert(ItemLabel(name.to_string()));
+            }
+            _ => console.print("labels are a single word"),
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use std::{fs, path::Path};
+
+use bevy::prelude::*;
+
+use crate::{
+    console::{Console, ConsoleAppExt, ConsoleCommand},
+    logic::memory::{Memory, words_from_hex},
+    selection::Selected,
+};
+
+pub struct ActionMemoryPlugin;
+impl Plugin for ActionMemoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_console_command(
+            "poke",
+            "poke <address> <word>..., write hexadecimal words into the selected memory",
+        )
+        .add_console_command(
+            "memload",
+            "memload <path>, load the selected memory from a hex (Logisim raw) or .bin file",
+        )
+        .add_console_command(
+            "memsave",
+            "memsave <path>, save the selected memory to a hex (Logisim raw) or .bin file",
+        )
+        .add_systems(Update, memory_commands);
+    }
+}
+
+fn parse_hex(text: &str) -> Result<u64, String> {
+    u64::from_str_radix(text.trim_start_matches("0x"), 16)
+        .map_err(|_| format!("invalid hexadecimal number `{text}`"))
+}
+
+/// Whether `path` names a raw binary file rather than a hex text one.
+fn is_binary(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("bin"))
+}
+
+fn memory_commands(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    mut memories: Query<&mut Memory, With<Selected>>,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        if !["poke", "memload", "memsave"].contains(&command.name.as_str()) {
+            continue;
+        }
+
+        let mut selected = memories.iter_mut();
+        let (Some(mut memory), None) = (selected.next(), selected.next()) else {
+            console.print("select exactly one RAM or ROM");
+            continue;
+        };
+        let args = command.args();
+
+        let result = match (command.name.as_str(), args.as_slice()) {
+            ("poke", [address, words @ ..]) if !words.is_empty() => {
+                poke(&mut memory, address, words)
+            }
+            ("poke", _) => Err("usage: poke <address> <word>...".to_string()),
+            ("memload", [path]) => load(&mut memory, path),
+            ("memload", _) => Err("usage: memload <path>".to_string()),
+            ("memsave", [path]) => save(&memory, path),
+            _ => Err("usage: memsave <path>".to_string()),
+        };
+        match result {
+            Ok(message) => console.print(message),
+            Err(error) => console.print(error),
+        }
+    }
+}
+
+fn poke(memory: &mut Memory, address: &str, words: &[&str]) -> Result<String, String> {
+    let address = parse_hex(address)? as usize;
+    let words = words
+        .iter()
+        .map(|word| parse_hex(word))
+        .collect::<Result<Vec<_>, _>>()?;
+    let end = address.checked_add(words.len());
+    if end.is_none_or(|end| end > memory.contents.len()) {
+        return Err(format!(
+            "the memory only has {} words",
+            memory.contents.len()
+        ));
+    }
+
+    for (offset, word) in words.iter().enumerate() {
+        memory.write(address + offset, *word);
+    }
+    Ok(format!("wrote {} words at {address:x}", words.len()))
+}
+
+fn load(memory: &mut Memory, path: &str) -> Result<String, String> {
+    let words = if is_binary(path) {
+        let bytes = fs::read(path).map_err(|error| format!("cannot read `{path}`: {error}"))?;
+        memory.words_from_binary(&bytes)
+    } else {
+        let text =
+            fs::read_to_string(path).map_err(|error| format!("cannot read `{path}`: {error}"))?;
+        words_from_hex(&text, memory.contents.len())?
+    };
+
+    if memory.load(&words) {
+        Ok(format!(
+            "loaded `{path}`, truncated to {} words",
+            memory.contents.len()
+        ))
+    } else {
+        Ok(format!("loaded {} words from `{path}`", words.len()))
+    }
+}
+
+fn save(memory: &Memory, path: &str) -> Result<String, String> {
+    let written = if is_binary(path) {
+        fs::write(path, memory.to_binary())
+    } else {
+        fs::write(path, memory.to_hex())
+    };
+    written.map_err(|error| format!("cannot write `{path}`: {error}"))?;
+    Ok(format!("saved {} words to `{path}`", memory.contents.len()))
+}
@@ -1,14 +1,23 @@
 use bevy::prelude::*;
 
-use crate::action::{button::ActionButtonPlugin, gate::ActionGatePlugin};
+use crate::action::{
+    button::ActionButtonPlugin, clock::ActionClockPlugin, gate::ActionGatePlugin,
+    label::ActionLabelPlugin, memory::ActionMemoryPlugin,
+};
 
 mod button;
+mod clock;
 mod gate;
+mod label;
+mod memory;
 
 pub struct ActionPlugin;
 impl Plugin for ActionPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(ActionGatePlugin)
-            .add_plugins(ActionButtonPlugin);
+            .add_plugins(ActionButtonPlugin)
+            .add_plugins(ActionMemoryPlugin)
+            .add_plugins(ActionClockPlugin)
+            .add_plugins(ActionLabelPlugin);
     }
 }
@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+
+use crate::{
+    analysis::{SelectedPorts, expression::Expr, extraction::Structure, sat::Cnf},
+    console::{Console, ConsoleAppExt, ConsoleCommand},
+};
+
+/// Most variables for which every input vector is tried, beyond which the
+/// expressions are handed to the SAT solver.
+const MAX_EXHAUSTIVE: usize = 16;
+
+pub struct EquivalencePlugin;
+impl Plugin for EquivalencePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_console_command(
+            "equiv",
+            "equiv [[name =] expression], compare the selected outputs sharing a label, \
+             or each selected output with an expression, inputs matching by label",
+        )
+        .add_systems(Update, equivalence_commands);
+    }
+}
+
+/// Values of the variables for which `a` and `b` differ, if there are any.
+fn counterexample(a: &Expr, b: &Expr) -> Option<Vec<(String, bool)>> {
+    let mut variables = a.variables();
+    variables.extend(b.variables());
+    variables.sort();
+    variables.dedup();
+
+    if variables.len() <= MAX_EXHAUSTIVE {
+        (0..1u32 << variables.len()).find_map(|row| {
+            let vector = variables
+                .iter()
+                .enumerate()
+                .map(|(index, name)| {
+                    let value = row >> (variables.len() - 1 - index) & 1 == 1;
+                    (name.clone(), value)
+                })
+                .collect::<Vec<_>>();
+            let value = |variable: &str| {
+                vector
+                    .iter()
+                    .any(|(name, value)| name == variable && *value)
+            };
+            (a.evaluate(&value) != b.evaluate(&value)).then_some(vector)
+        })
+    } else {
+        let mut cnf = Cnf::default();
+        let miter = cnf.encode(&Expr::Xor(Box::new(a.clone()), Box::new(b.clone())));
+        cnf.add(vec![miter]);
+        let model = cnf.solve()?;
+        let vector = variables.into_iter().map(|name| {
+            let value = model.get(&name).copied().unwrap_or(false);
+            (name, value)
+        });
+        Some(vector.collect())
+    }
+}
+
+/// Tells whether `a` and `b` compute the same function.
+fn compare(name: &str, a: &Expr, b: &Expr) -> String {
+    let Some(vector) = counterexample(a, b) else {
+        return format!("{name}: equivalent");
+    };
+
+    let value = |variable: &str| {
+        vector
+            .iter()
+            .any(|(name, value)| name == variable && *value)
+    };
+    let assignments = vector
+        .iter()
+        .map(|(name, value)| format!("{name}={}", *value as u8))
+        .collect::<Vec<_>>();
+    format!(
+        "{name}: differ for {} ({} vs {})",
+        assignments.join(" "),
+        a.evaluate(&value) as u8,
+        b.evaluate(&value) as u8
+    )
+}
+
+fn equivalence_commands(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    ports: SelectedPorts,
+    structure: Structure,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        if command.name != "equiv" {
+            continue;
+        }
+
+        let (_, outputs) = ports.ports();
+        let mut expressions: Vec<(String, Vec<Expr>)> = Vec::new();
+        for output in outputs {
+            let expr = match structure.expression(output.entity) {
+                Ok(expr) => expr,
+                Err(error) => {
+                    console.print(format!("{}: {error}", output.name));
+                    continue;
+                }
+            };
+            match expressions
+                .iter_mut()
+                .find(|(name, _)| *name == output.name)
+            {
+                Some((_, exprs)) => exprs.push(expr),
+                None => expressions.push((output.name, vec![expr])),
+            }
+        }
+
+        if command.arguments.trim().is_empty() {
+            let pairs = expressions
+                .iter()
+                .filter_map(|(name, exprs)| match exprs.as_slice() {
+                    [a, b] => Some(compare(name, a, b)),
+                    _ => None,
+                });
+            let reports = pairs.collect::<Vec<_>>();
+            if reports.is_empty() {
+                console.print("select two sub-circuits whose outputs have the same labels");
+            }
+            for report in reports {
+                console.print(report);
+            }
+            continue;
+        }
+
+        let (target, expression) = match command.arguments.split_once('=') {
+            Some((name, expression)) => (Some(name.trim()), expression),
+            None => (None, command.arguments.as_str()),
+        };
+        let reference = match Expr::parse(expression) {
+            Ok(expr) => expr,
+            Err(error) => {
+                console.print(error);
+                continue;
+            }
+        };
+
+        let compared = expressions
+            .iter()
+            .filter(|(name, _)| target.is_none_or(|target| target == name))
+            .flat_map(|(name, exprs)| exprs.iter().map(move |expr| (name, expr)))
+            .map(|(name, expr)| compare(name, expr, &reference))
+            .collect::<Vec<_>>();
+        if compared.is_empty() {
+            console.print("no output to compare selected");
+        }
+        for report in compared {
+            console.print(report);
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use std::fmt;
+
+use crate::analysis::{
+    minimize::{Implicant, minimize},
+    synthesis::MAX_VARIABLES,
+};
+
+/// A boolean expression over named single-bit variables.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expr {
+    Constant(bool),
+    Variable(String),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Xor(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Parses an expression using `!` or `~` for NOT, `&` or `*` for AND,
+    /// `^` for XOR and `|` or `+` for OR, from the tightest to the loosest.
+    pub fn parse(text: &str) -> Result<Expr, String> {
+        let mut parser = Parser {
+            tokens: tokenize(text)?,
+            position: 0,
+        };
+        let expr = parser.or()?;
+        match parser.tokens.get(parser.position) {
+            None => Ok(expr),
+            Some(token) => Err(format!("unexpected `{token}`")),
+        }
+    }
+
+    /// The variables the expression uses, sorted by name.
+    pub fn variables(&self) -> Vec<String> {
+        let mut variables = Vec::new();
+        self.collect_variables(&mut variables);
+        variables.sort();
+        variables.dedup();
+        variables
+    }
+
+    fn collect_variables(&self, variables: &mut Vec<String>) {
+        match self {
+            Expr::Constant(_) => {}
+            Expr::Variable(name) => variables.push(name.clone()),
+            Expr::Not(a) => a.collect_variables(variables),
+            Expr::And(a, b) | Expr::Or(a, b) | Expr::Xor(a, b) => {
+                a.collect_variables(variables);
+                b.collect_variables(variables);
+            }
+        }
+    }
+
+    /// The value of the expression, `value` giving that of each variable.
+    pub fn evaluate(&self, value: &impl Fn(&str) -> bool) -> bool {
+        match self {
+            Expr::Constant(constant) => *constant,
+            Expr::Variable(name) => value(name),
+            Expr::Not(a) => !a.evaluate(value),
+            Expr::And(a, b) => a.evaluate(value) && b.evaluate(value),
+            Expr::Or(a, b) => a.evaluate(value) || b.evaluate(value),
+            Expr::Xor(a, b) => a.evaluate(value) != b.evaluate(value),
+        }
+    }
+
+    /// A sum of products equivalent to the expression, minimal as far as
+    /// [`minimize`] goes, or the expression itself when it has too many
+    /// variables to minimize.
+    pub fn simplify(&self) -> Expr {
+        let variables = self.variables();
+        if variables.len() > MAX_VARIABLES {
+            return self.clone();
+        }
+
+        let ones = (0..1u32 << variables.len())
+            .filter(|row| {
+                self.evaluate(&|variable: &str| {
+                    let index = variables.iter().position(|other| other == variable);
+                    index.is_some_and(|index| row >> (variables.len() - 1 - index) & 1 == 1)
+                })
+            })
+            .collect::<Vec<_>>();
+        Expr::sum_of_products(&variables, &minimize(variables.len(), &ones, &[]))
+    }
+
+    /// The OR of `terms` over `variables`, the first one being the most
+    /// significant bit of the implicants.
+    pub fn sum_of_products(variables: &[String], terms: &[Implicant]) -> Expr {
+        let product = |term: &Implicant| {
+            let literals = variables
+                .iter()
+                .enumerate()
+                .filter_map(|(index, variable)| {
+                    let bit = 1 << (variables.len() - 1 - index);
+                    let literal = Expr::Variable(variable.clone());
+                    (term.mask & bit != 0).then(|| match term.value & bit {
+                        0 => Expr::Not(Box::new(literal)),
+                        _ => literal,
+                    })
+                });
+            literals
+                .reduce(|a, b| Expr::And(Box::new(a), Box::new(b)))
+                .unwrap_or(Expr::Constant(true))
+        };
+        terms
+            .iter()
+            .map(product)
+            .reduce(|a, b| Expr::Or(Box::new(a), Box::new(b)))
+            .unwrap_or(Expr::Constant(false))
+    }
+}
+
+impl Expr {
+    /// How tightly the expression binds, to know where parentheses go.
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Or(..) => 0,
+            Expr::Xor(..) => 1,
+            Expr::And(..) => 2,
+            Expr::Not(_) => 3,
+            Expr::Constant(_) | Expr::Variable(_) => 4,
+        }
+    }
+
+    /// Writes `self` as an operand of an operator of the given precedence.
+    fn fmt_operand(&self, f: &mut fmt::Formatter, precedence: u8) -> fmt::Result {
+        if self.precedence() < precedence {
+            write!(f, "({self})")
+        } else {
+            write!(f, "{self}")
+        }
+    }
+}
+
+/// Writes the expression in the syntax `Expr::parse` reads back.
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (a, operator, b) = match self {
+            Expr::Constant(constant) => return write!(f, "{}", *constant as u8),
+            Expr::Variable(name) => return write!(f, "{name}"),
+            Expr::Not(a) => {
+                write!(f, "!")?;
+                return a.fmt_operand(f, self.precedence());
+            }
+            Expr::And(a, b) => (a, "&", b),
+            Expr::Or(a, b) => (a, "|", b),
+            Expr::Xor(a, b) => (a, "^", b),
+        };
+        a.fmt_operand(f, self.precedence())?;
+        write!(f, " {operator} ")?;
+        b.fmt_operand(f, self.precedence() + 1)
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '!' | '~' | '&' | '*' | '^' | '|' | '+' | '(' | ')' => tokens.push(c.to_string()),
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut name = c.to_string();
+                while let Some(&c) = chars.peek()
+                    && (c.is_alphanumeric() || c == '_')
+                {
+                    name.push(c);
+                    chars.next();
+                }
+                tokens.push(name);
+            }
+            c => return Err(format!("unexpected `{c}`")),
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<String>,
+    position: usize,
+}
+
+impl Parser {
+    /// Consumes the next token if it is one of `operators`.
+    fn eat(&mut self, operators: &[&str]) -> bool {
+        let found = self
+            .tokens
+            .get(self.position)
+            .is_some_and(|token| operators.contains(&token.as_str()));
+        self.position += found as usize;
+        found
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut expr = self.xor()?;
+        while self.eat(&["|", "+"]) {
+            expr = Expr::Or(Box::new(expr), Box::new(self.xor()?));
+        }
+        Ok(expr)
+    }
+
+    fn xor(&mut self) -> Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.eat(&["^"]) {
+            expr = Expr::Xor(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.not()?;
+        while self.eat(&["&", "*"]) {
+            expr = Expr::And(Box::new(expr), Box::new(self.not()?));
+        }
+        Ok(expr)
+    }
+
+    fn not(&mut self) -> Result<Expr, String> {
+        if self.eat(&["!", "~"]) {
+            return Ok(Expr::Not(Box::new(self.not()?)));
+        }
+        if self.eat(&["("]) {
+            let expr = self.or()?;
+            if !self.eat(&[")"]) {
+                return Err("missing `)`".to_string());
+            }
+            return Ok(expr);
+        }
+
+        let Some(token) = self.tokens.get(self.position).cloned() else {
+            return Err("unexpected end of expression".to_string());
+        };
+        self.position += 1;
+        match token.as_str() {
+            "0" => Ok(Expr::Constant(false)),
+            "1" => Ok(Expr::Constant(true)),
+            name if name.starts_with(|c: char| c.is_alphabetic() || c == '_') => {
+                Ok(Expr::Variable(token))
+            }
+            _ => Err(format!("unexpected `{token}`")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variable(name: &str) -> Box<Expr> {
+        Box::new(Expr::Variable(name.to_string()))
+    }
+
+    #[test]
+    fn operators_bind_from_not_to_or() {
+        let expr = Expr::parse("a | b ^ c & !d").unwrap();
+        let and = Expr::And(variable("c"), Box::new(Expr::Not(variable("d"))));
+        let xor = Expr::Xor(variable("b"), Box::new(and));
+        assert_eq!(expr, Expr::Or(variable("a"), Box::new(xor)));
+        assert_eq!(
+            Expr::parse("~a * (b + 1)").unwrap(),
+            Expr::parse("!a & (b | 1)").unwrap()
+        );
+    }
+
+    #[test]
+    fn display_is_read_back() {
+        for text in [
+            "(a | b) & !(c ^ d_1)",
+            "a ^ (b ^ c)",
+            "!!a | 0",
+            "x & y & z",
+        ] {
+            let expr = Expr::parse(text).unwrap();
+            assert_eq!(Expr::parse(&expr.to_string()).unwrap(), expr, "{text}");
+        }
+        assert_eq!(
+            Expr::parse("a & (b | c)").unwrap().to_string(),
+            "a & (b | c)"
+        );
+    }
+
+    #[test]
+    fn malformed_expressions_are_rejected() {
+        for text in ["", "a &", "(a | b", "a b", "a $ b", "2", ")"] {
+            assert!(Expr::parse(text).is_err(), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn simplify_keeps_the_function() {
+        let expr = Expr::parse("a & b | a & !b | c & 0").unwrap();
+        assert_eq!(expr.simplify(), Expr::Variable("a".to_string()));
+        assert_eq!(expr.variables(), ["a", "b", "c"]);
+
+        let expr = Expr::parse("a ^ b ^ c").unwrap();
+        let simplified = expr.simplify();
+        for row in 0..8 {
+            let value = |name: &str| row >> (b'c' - name.as_bytes()[0]) & 1 == 1;
+            assert_eq!(simplified.evaluate(&value), expr.evaluate(&value));
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use bevy::{ecs::system::SystemParam, platform::collections::HashMap, prelude::*};
+use ergo_core::simulation::Wiring;
+
+use crate::{
+    analysis::{SelectedPorts, expression::Expr, port_name},
+    console::{Console, ConsoleAppExt, ConsoleCommand},
+    logic::{Gate, ItemLabel, LogicButton, Nets, source::Constant},
+    signal::Level,
+};
+
+pub struct ExtractionPlugin;
+impl Plugin for ExtractionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_console_command(
+            "expr",
+            "expr, print the simplified boolean expression of each selected output",
+        )
+        .add_systems(Update, extraction_commands);
+    }
+}
+
+/// Reads the gates back into boolean expressions over the items feeding
+/// them: buttons, and any other component, taken as an opaque variable.
+#[derive(SystemParam)]
+pub struct Structure<'w, 's> {
+    nets: Nets<'w, 's>,
+    gates: Query<'w, 's, &'static Gate>,
+    buttons: Query<'w, 's, (), With<LogicButton>>,
+    constants: Query<'w, 's, &'static Constant>,
+    labels: Query<'w, 's, &'static ItemLabel>,
+}
+
+/// The state of a walk from an output back to its sources.
+#[derive(Default)]
+struct Walk {
+    /// The gates between the output and the item being read.
+    path: Vec<Entity>,
+    expressions: HashMap<Entity, Expr>,
+}
+
+impl Structure<'_, '_> {
+    /// The expression of the net `entity` belongs to.
+    pub fn expression(&self, entity: Entity) -> Result<Expr, String> {
+        self.net(entity, &mut Walk::default())
+    }
+
+    /// The name a source goes by: its label, or one made from its entity.
+    fn name(&self, entity: Entity) -> String {
+        let label = self.labels.get(entity).ok();
+        match label {
+            _ if self.buttons.contains(entity) => port_name(entity, true, label, None),
+            Some(label) => label.0.clone(),
+            None => format!("n{}", entity.index()),
+        }
+    }
+
+    fn net(&self, entity: Entity, walk: &mut Walk) -> Result<Expr, String> {
+        let (drivers, pull) = self.nets.pulled_drivers(entity);
+        match (drivers.as_slice(), pull) {
+            ([driver], _) => self.driver(*driver, walk),
+            ([], Level::Low) => Ok(Expr::Constant(false)),
+            ([], Level::High) => Ok(Expr::Constant(true)),
+            ([], _) => Err(format!("{} is not driven", self.name(entity))),
+            (_, _) => Err(format!("{} has several drivers", self.name(entity))),
+        }
+    }
+
+    fn driver(&self, entity: Entity, walk: &mut Walk) -> Result<Expr, String> {
+        if let Some(expr) = walk.expressions.get(&entity) {
+            return Ok(expr.clone());
+        }
+        if let Some(start) = walk.path.iter().position(|gate| *gate == entity) {
+            let cycle = walk.path[start..]
+                .iter()
+                .map(|gate| self.name(*gate))
+                .collect::<Vec<_>>();
+            return Err(format!("cycle through {}", cycle.join(" -> ")));
+        }
+
+        let Ok(gate) = self.gates.get(entity) else {
+            return match self.constants.get(entity).map(Constant::signal) {
+                Ok(signal) if signal.width() == 1 => Ok(Expr::Constant(signal.is_high())),
+                Ok(_) => Err(format!("{} is wider than a bit", self.name(entity))),
+                Err(_) => Ok(Expr::Variable(self.name(entity))),
+            };
+        };
+
+        walk.path.push(entity);
+        let mut input = |input: &Option<Entity>| match input {
+            Some(input) => self.net(*input, walk).map(Box::new),
+            None => Err(format!("{} has an unplugged input", self.name(entity))),
+        };
+        let expr = match gate {
+            Gate::And(a, b) => Expr::And(input(a)?, input(b)?),
+            Gate::Or(a, b) => Expr::Or(input(a)?, input(b)?),
+            Gate::Not(a) => Expr::Not(input(a)?),
+            Gate::TriState(..) => {
+                return Err(format!("{} is a tri-state buffer", self.name(entity)));
+            }
+        };
+        walk.path.pop();
+
+        walk.expressions.insert(entity, expr.clone());
+        Ok(expr)
+    }
+}
+
+fn extraction_commands(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    ports: SelectedPorts,
+    structure: Structure,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        if command.name != "expr" {
+            continue;
+        }
+
+        let (_, outputs) = ports.ports();
+        if outputs.is_empty() {
+            console.print("no output selected");
+        }
+        for output in outputs {
+            match structure.expression(output.entity) {
+                Ok(expr) => console.print(format!("{} = {}", output.name, expr.simplify())),
+                Err(error) => console.print(format!("{}: {error}", output.name)),
+            }
+        }
+    }
+}
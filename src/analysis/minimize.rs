@@ -0,0 +1,237 @@
+use bevy::platform::collections::HashSet;
+
+/// Most variables a function may have for its cover to be searched exactly,
+/// larger ones being covered greedily.
+pub const MAX_EXACT_VARIABLES: usize = 6;
+
+/// A product term over `n` variables: the variables whose bit is set in
+/// `mask` must equal their bit in `value`, the others do not matter. The
+/// first variable is the most significant bit.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Implicant {
+    pub value: u32,
+    pub mask: u32,
+}
+
+impl Implicant {
+    fn covers(&self, minterm: u32) -> bool {
+        minterm & self.mask == self.value
+    }
+
+    /// Joins two implicants differing in a single variable.
+    fn merge(&self, other: &Implicant) -> Option<Implicant> {
+        let difference = self.value ^ other.value;
+        (self.mask == other.mask && difference.count_ones() == 1).then_some(Implicant {
+            value: self.value & !difference,
+            mask: self.mask & !difference,
+        })
+    }
+}
+
+/// A sum of products for the function of `variables` variables at 1 on
+/// `ones`, whatever on `dont_cares` and at 0 elsewhere. The prime implicants
+/// are found by Quine–McCluskey, then the essential ones are taken. The
+/// remaining minterms are covered exactly, with the fewest terms then the
+/// fewest literals, up to [`MAX_EXACT_VARIABLES`] variables, and past that
+/// greedily by the primes covering the most of them, which may take a few
+/// more terms than needed.
+pub fn minimize(variables: usize, ones: &[u32], dont_cares: &[u32]) -> Vec<Implicant> {
+    let full_mask = ((1u64 << variables) - 1) as u32;
+    let mut current = ones
+        .iter()
+        .chain(dont_cares)
+        .map(|minterm| Implicant {
+            value: *minterm,
+            mask: full_mask,
+        })
+        .collect::<HashSet<_>>();
+
+    let mut primes = Vec::new();
+    while !current.is_empty() {
+        let terms = current.iter().copied().collect::<Vec<_>>();
+        let mut merged = HashSet::new();
+        let mut next = HashSet::new();
+        for (index, a) in terms.iter().enumerate() {
+            for b in &terms[index + 1..] {
+                if let Some(joined) = a.merge(b) {
+                    merged.insert(*a);
+                    merged.insert(*b);
+                    next.insert(joined);
+                }
+            }
+        }
+        primes.extend(terms.into_iter().filter(|term| !merged.contains(term)));
+        current = next;
+    }
+
+    let mut uncovered = ones.iter().copied().collect::<HashSet<_>>();
+    let mut cover = Vec::new();
+    let essentials = ones.iter().filter_map(|minterm| {
+        let mut covering = primes.iter().filter(|prime| prime.covers(*minterm));
+        match (covering.next(), covering.next()) {
+            (Some(prime), None) => Some(*prime),
+            _ => None,
+        }
+    });
+    for prime in essentials.collect::<Vec<_>>() {
+        if !cover.contains(&prime) {
+            uncovered.retain(|minterm| !prime.covers(*minterm));
+            cover.push(prime);
+        }
+    }
+
+    if variables <= MAX_EXACT_VARIABLES {
+        let minterms = uncovered.drain().collect::<Vec<_>>();
+        let mut best = None;
+        search(&primes, &minterms, &mut cover, &mut best);
+        cover = best.unwrap_or(cover);
+    }
+    while !uncovered.is_empty() {
+        let best = primes.iter().max_by_key(|prime| {
+            let covered = uncovered.iter().filter(|m| prime.covers(**m)).count();
+            (covered, u32::MAX - prime.mask.count_ones())
+        });
+        let Some(best) = best.copied() else {
+            break;
+        };
+        uncovered.retain(|minterm| !best.covers(*minterm));
+        cover.push(best);
+    }
+
+    cover.sort_by_key(|term| (term.mask.count_ones(), term.value));
+    cover
+}
+
+/// The number of terms and of literals of a cover.
+fn cost(cover: &[Implicant]) -> (usize, u32) {
+    let literals = cover.iter().map(|term| term.mask.count_ones()).sum();
+    (cover.len(), literals)
+}
+
+/// Completes `chosen` with the primes covering `uncovered`, keeping in `best`
+/// the cheapest cover found.
+fn search(
+    primes: &[Implicant],
+    uncovered: &[u32],
+    chosen: &mut Vec<Implicant>,
+    best: &mut Option<Vec<Implicant>>,
+) {
+    if let Some(best) = best
+        && (cost(chosen) >= cost(best) || !uncovered.is_empty() && chosen.len() >= best.len())
+    {
+        return;
+    }
+    // Branching on the minterm the fewest primes cover keeps the search small.
+    let covering = |minterm: u32| primes.iter().filter(move |prime| prime.covers(minterm));
+    let Some(minterm) = uncovered
+        .iter()
+        .copied()
+        .min_by_key(|minterm| covering(*minterm).count())
+    else {
+        *best = Some(chosen.clone());
+        return;
+    };
+
+    for prime in covering(minterm) {
+        let rest = uncovered
+            .iter()
+            .copied()
+            .filter(|minterm| !prime.covers(*minterm))
+            .collect::<Vec<_>>();
+        chosen.push(*prime);
+        search(primes, &rest, chosen, best);
+        chosen.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Whether the terms give 1 exactly on `ones`, ignoring `dont_cares`.
+    fn computes(terms: &[Implicant], variables: usize, ones: &[u32], dont_cares: &[u32]) -> bool {
+        (0..1 << variables).all(|minterm| {
+            let value = terms.iter().any(|term| term.covers(minterm));
+            dont_cares.contains(&minterm) || value == ones.contains(&minterm)
+        })
+    }
+
+    #[test]
+    fn covers_exactly_the_ones() {
+        let (ones, dont_cares) = ([1, 3, 7, 11, 15], [0, 2, 5]);
+        let terms = minimize(4, &ones, &dont_cares);
+        assert!(computes(&terms, 4, &ones, &dont_cares));
+        assert_eq!(terms.len(), 2);
+        assert!(minimize(3, &[], &[]).is_empty());
+        assert_eq!(
+            minimize(2, &[0, 1, 2, 3], &[]),
+            [Implicant { value: 0, mask: 0 }]
+        );
+    }
+
+    #[test]
+    fn cyclic_functions_get_the_fewest_terms() {
+        // Every minterm is covered by two primes, none of them essential.
+        let ones = [0, 1, 2, 5, 6, 7];
+        let terms = minimize(3, &ones, &[]);
+        assert!(computes(&terms, 3, &ones, &[]));
+        assert_eq!(terms.len(), 3);
+    }
+
+    #[test]
+    fn every_three_variable_function_is_minimal() {
+        let cubes = (0..27u32)
+            .map(|index| {
+                let (mut value, mut mask) = (0, 0);
+                for bit in 0..3 {
+                    match index / 3u32.pow(bit) % 3 {
+                        0 => {}
+                        digit => {
+                            mask |= 1 << bit;
+                            value |= (digit - 1) << bit;
+                        }
+                    }
+                }
+                Implicant { value, mask }
+            })
+            .collect::<Vec<_>>();
+
+        for function in 0..256u32 {
+            let ones = (0..8)
+                .filter(|m| function >> m & 1 == 1)
+                .collect::<Vec<_>>();
+            let implicants = cubes
+                .iter()
+                .filter(|cube| (0..8).all(|m| !cube.covers(m) || ones.contains(&m)))
+                .collect::<Vec<_>>();
+            // The primes are the implicants no other one contains.
+            let implicants = implicants
+                .iter()
+                .filter(|a| {
+                    !implicants
+                        .iter()
+                        .any(|b| a != &b && b.mask & !a.mask == 0 && a.value & b.mask == b.value)
+                })
+                .copied()
+                .copied()
+                .collect::<Vec<_>>();
+            // The fewest primes covering the ones, by brute force.
+            let fewest = (0..=4)
+                .find(|size| {
+                    (0..1u32 << implicants.len())
+                        .filter(|set| set.count_ones() == *size)
+                        .any(|set| {
+                            ones.iter().all(|m| {
+                                (0..implicants.len())
+                                    .any(|i| set >> i & 1 == 1 && implicants[i].covers(*m))
+                            })
+                        })
+                })
+                .unwrap();
+
+            let terms = minimize(3, &ones, &[]);
+            assert!(computes(&terms, 3, &ones, &[]));
+            assert_eq!(terms.len() as u32, fewest, "function {function:08b}");
+        }
+    }
+}
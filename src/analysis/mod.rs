@@ -0,0 +1,92 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    analysis::{
+        equivalence::EquivalencePlugin, extraction::ExtractionPlugin, synthesis::SynthesisPlugin,
+        truth_table::TruthTablePlugin,
+    },
+    logic::{Item, ItemLabel, LogicButton, Tunnel, Value},
+    selection::Selected,
+};
+
+pub mod equivalence;
+pub mod expression;
+pub mod extraction;
+pub mod minimize;
+pub mod network;
+pub mod sat;
+pub mod synthesis;
+pub mod truth_table;
+
+/// Commands studying the circuit as a whole rather than simulating it.
+pub struct AnalysisPlugin;
+impl Plugin for AnalysisPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(TruthTablePlugin)
+            .add_plugins(SynthesisPlugin)
+            .add_plugins(ExtractionPlugin)
+            .add_plugins(EquivalencePlugin);
+    }
+}
+
+/// An input or output of the circuit under study, with the name it goes by.
+pub struct Port {
+    pub entity: Entity,
+    pub name: String,
+}
+
+/// The name an input or output goes by: its label, the name of its tunnel,
+/// or one made from its entity, so that every analysis agrees on it.
+pub fn port_name(
+    entity: Entity,
+    is_input: bool,
+    label: Option<&ItemLabel>,
+    tunnel: Option<&Tunnel>,
+) -> String {
+    match (label, tunnel) {
+        (Some(label), _) => label.0.clone(),
+        (None, Some(tunnel)) => tunnel.name.clone(),
+        (None, None) if is_input => format!("in{}", entity.index()),
+        (None, None) => format!("out{}", entity.index()),
+    }
+}
+
+/// The selected items an analysis works on: the buttons are its inputs, the
+/// other items with a value its outputs. Both are sorted in reading order,
+/// top to bottom then left to right.
+#[derive(SystemParam)]
+#[allow(clippy::type_complexity)]
+pub struct SelectedPorts<'w, 's> {
+    items: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static GlobalTransform,
+            Has<LogicButton>,
+            Option<&'static ItemLabel>,
+            Option<&'static Tunnel>,
+        ),
+        (With<Item>, With<Selected>, With<Value>),
+    >,
+}
+
+impl SelectedPorts<'_, '_> {
+    pub fn ports(&self) -> (Vec<Port>, Vec<Port>) {
+        let mut items = self.items.iter().collect::<Vec<_>>();
+        items.sort_by(|(_, a, ..), (_, b, ..)| {
+            let (a, b) = (a.translation(), b.translation());
+            b.y.total_cmp(&a.y).then(a.x.total_cmp(&b.x))
+        });
+
+        let (mut inputs, mut outputs) = (Vec::new(), Vec::new());
+        for (entity, _, is_input, label, tunnel) in items {
+            let ports = if is_input { &mut inputs } else { &mut outputs };
+            ports.push(Port {
+                entity,
+                name: port_name(entity, is_input, label, tunnel),
+            });
+        }
+        (inputs, outputs)
+    }
+}
@@ -0,0 +1,205 @@
+use bevy::{platform::collections::HashMap, prelude::*};
+
+use crate::{
+    analysis::minimize::Implicant,
+    logic::{Gate, ItemLabel, Junction, LogicButton, source::Constant},
+};
+
+/// Horizontal distance between two layers of a placed network.
+const LAYER_SPACING: f32 = 90.0;
+/// Vertical distance between two items of a layer.
+const ROW_SPACING: f32 = 50.0;
+
+/// An item of a network, referring to its inputs by index. Inputs always
+/// come before the nodes using them.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Node {
+    Input(String),
+    Constant(bool),
+    Not(usize),
+    And(usize, usize),
+    Or(usize, usize),
+}
+
+/// A network of gates built by a synthesis or an import, before it is
+/// spawned on the canvas.
+#[derive(Default)]
+pub struct Network {
+    pub nodes: Vec<Node>,
+    /// The name of each output and the node driving it.
+    pub outputs: Vec<(String, usize)>,
+    /// The nodes added through `add`, to share them.
+    shared: HashMap<Node, usize>,
+}
+
+impl Network {
+    /// Adds `node`, or gives the same one added before.
+    pub fn add(&mut self, node: Node) -> usize {
+        if let Some(index) = self.shared.get(&node) {
+            return *index;
+        }
+        let index = self.push(node.clone());
+        self.shared.insert(node, index);
+        index
+    }
+
+    /// Adds `node` even if the same one was added before, so that it gets
+    /// its own gate.
+    pub fn push(&mut self, node: Node) -> usize {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    pub fn input(&mut self, name: &str) -> usize {
+        self.add(Node::Input(name.to_string()))
+    }
+
+    /// Joins `nodes` with a balanced tree of two-input gates, giving
+    /// `empty` when there is nothing to join.
+    fn tree(&mut self, nodes: &[usize], gate: fn(usize, usize) -> Node, empty: bool) -> usize {
+        match nodes {
+            [] => self.add(Node::Constant(empty)),
+            [node] => *node,
+            _ => {
+                let (left, right) = nodes.split_at(nodes.len() / 2);
+                let left = self.tree(left, gate, empty);
+                let right = self.tree(right, gate, empty);
+                self.add(gate(left, right))
+            }
+        }
+    }
+
+    pub fn and_all(&mut self, nodes: &[usize]) -> usize {
+        self.tree(nodes, Node::And, true)
+    }
+
+    pub fn or_all(&mut self, nodes: &[usize]) -> usize {
+        self.tree(nodes, Node::Or, false)
+    }
+
+    /// Adds the output `name` computing a sum of `terms` over `inputs`.
+    pub fn add_sum_of_products(&mut self, name: &str, inputs: &[String], terms: &[Implicant]) {
+        let products = terms
+            .iter()
+            .map(|term| {
+                let literals = inputs
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, input)| {
+                        let bit = 1 << (inputs.len() - 1 - index);
+                        if term.mask & bit == 0 {
+                            return None;
+                        }
+                        let variable = self.input(input);
+                        Some(if term.value & bit != 0 {
+                            variable
+                        } else {
+                            self.add(Node::Not(variable))
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                self.and_all(&literals)
+            })
+            .collect::<Vec<_>>();
+        let sum = self.or_all(&products);
+        self.outputs.push((name.to_string(), sum));
+    }
+
+    /// The layer of each node, inputs and constants being in layer 0 and
+    /// each gate one layer after its last input.
+    fn layers(&self) -> Vec<usize> {
+        let mut layers: Vec<usize> = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let layer = match node {
+                Node::Input(_) | Node::Constant(_) => 0,
+                Node::Not(a) => layers[*a] + 1,
+                Node::And(a, b) | Node::Or(a, b) => layers[*a].max(layers[*b]) + 1,
+            };
+            layers.push(layer);
+        }
+        layers
+    }
+
+    fn node_inputs(&self, index: usize) -> Vec<usize> {
+        match self.nodes[index] {
+            Node::Input(_) | Node::Constant(_) => vec![],
+            Node::Not(a) => vec![a],
+            Node::And(a, b) | Node::Or(a, b) => vec![a, b],
+        }
+    }
+
+    /// Positions of the nodes then of the outputs, relative to the top left
+    /// corner. Layers go left to right; within a layer items are sorted by
+    /// the mean height of their inputs, which keeps links from crossing
+    /// much.
+    fn layout(&self) -> (Vec<Vec2>, Vec<Vec2>) {
+        let layers = self.layers();
+        let last_layer = layers.iter().copied().max().unwrap_or(0);
+        let mut positions = vec![Vec2::ZERO; self.nodes.len()];
+        let barycenter = |inputs: &[usize], positions: &[Vec2]| {
+            inputs.iter().map(|input| positions[*input].y).sum::<f32>() / inputs.len().max(1) as f32
+        };
+
+        for layer in 0..=last_layer {
+            let mut members = (0..self.nodes.len())
+                .filter(|index| layers[*index] == layer)
+                .map(|index| (index, barycenter(&self.node_inputs(index), &positions)))
+                .collect::<Vec<_>>();
+            if layer > 0 {
+                members.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+            }
+            for (row, (index, _)) in members.into_iter().enumerate() {
+                positions[index] =
+                    Vec2::new(layer as f32 * LAYER_SPACING, -(row as f32) * ROW_SPACING);
+            }
+        }
+
+        let mut outputs = self
+            .outputs
+            .iter()
+            .enumerate()
+            .map(|(index, (_, node))| (index, positions[*node].y))
+            .collect::<Vec<_>>();
+        outputs.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        let mut output_positions = vec![Vec2::ZERO; self.outputs.len()];
+        for (row, (index, _)) in outputs.into_iter().enumerate() {
+            output_positions[index] = Vec2::new(
+                (last_layer + 1) as f32 * LAYER_SPACING,
+                -(row as f32) * ROW_SPACING,
+            );
+        }
+        (positions, output_positions)
+    }
+
+    /// Spawns the network with its top left corner at `origin`: a labelled
+    /// button per input, the gates, and a labelled junction per output.
+    pub fn spawn(&self, commands: &mut Commands, origin: Vec2) {
+        let (positions, output_positions) = self.layout();
+        let mut entities: Vec<Entity> = Vec::with_capacity(self.nodes.len());
+
+        for (node, position) in self.nodes.iter().zip(positions) {
+            let mut entity = match node {
+                Node::Input(name) => {
+                    commands.spawn((LogicButton::default(), ItemLabel(name.clone())))
+                }
+                Node::Constant(true) => commands.spawn(Constant::Power),
+                Node::Constant(false) => commands.spawn(Constant::Ground),
+                Node::Not(a) => commands.spawn(Gate::Not(Some(entities[*a]))),
+                Node::And(a, b) => {
+                    commands.spawn(Gate::And(Some(entities[*a]), Some(entities[*b])))
+                }
+                Node::Or(a, b) => commands.spawn(Gate::Or(Some(entities[*a]), Some(entities[*b]))),
+            };
+            entity.insert(Transform::from_translation((origin + position).extend(0.0)));
+            entities.push(entity.id());
+        }
+
+        for ((name, node), position) in self.outputs.iter().zip(output_positions) {
+            commands.spawn((
+                Junction(Some(entities[*node])),
+                ItemLabel(name.clone()),
+                Transform::from_translation((origin + position).extend(0.0)),
+            ));
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use bevy::platform::collections::HashMap;
+
+use crate::analysis::expression::Expr;
+
+/// A formula in conjunctive normal form. Variables are numbered from 1, a
+/// negative literal standing for the negation of its variable.
+#[derive(Default)]
+pub struct Cnf {
+    variables: usize,
+    clauses: Vec<Vec<i32>>,
+    /// The variable standing for each variable of the encoded expressions.
+    names: HashMap<String, i32>,
+}
+
+impl Cnf {
+    fn variable(&mut self) -> i32 {
+        self.variables += 1;
+        self.variables as i32
+    }
+
+    pub fn add(&mut self, clause: Vec<i32>) {
+        self.clauses.push(clause);
+    }
+
+    /// Adds the clauses of the Tseitin encoding of `expr`, giving the literal
+    /// equal to its value.
+    pub fn encode(&mut self, expr: &Expr) -> i32 {
+        let (a, b) = match expr {
+            Expr::Constant(constant) => {
+                let variable = self.variable();
+                self.add(vec![if *constant { variable } else { -variable }]);
+                return variable;
+            }
+            Expr::Variable(name) => {
+                if let Some(variable) = self.names.get(name) {
+                    return *variable;
+                }
+                let variable = self.variable();
+                self.names.insert(name.clone(), variable);
+                return variable;
+            }
+            Expr::Not(a) => return -self.encode(a),
+            Expr::And(a, b) | Expr::Or(a, b) | Expr::Xor(a, b) => (self.encode(a), self.encode(b)),
+        };
+
+        let v = self.variable();
+        let clauses = match expr {
+            Expr::And(..) => vec![vec![-v, a], vec![-v, b], vec![v, -a, -b]],
+            Expr::Or(..) => vec![vec![v, -a], vec![v, -b], vec![-v, a, b]],
+            _ => vec![
+                vec![-v, a, b],
+                vec![-v, -a, -b],
+                vec![v, -a, b],
+                vec![v, a, -b],
+            ],
+        };
+        self.clauses.extend(clauses);
+        v
+    }
+
+    /// The value of each variable of the encoded expressions in some
+    /// assignment satisfying every clause, if there is one.
+    pub fn solve(&self) -> Option<HashMap<String, bool>> {
+        let mut assignment = vec![None; self.variables + 1];
+        if !self.search(&mut assignment) {
+            return None;
+        }
+        let model = self.names.iter().map(|(name, variable)| {
+            let value = assignment[*variable as usize].unwrap_or(false);
+            (name.clone(), value)
+        });
+        Some(model.collect())
+    }
+
+    /// Davis–Putnam–Logemann–Loveland: propagates the unit clauses, then
+    /// tries both values of a variable of some unsatisfied clause.
+    fn search(&self, assignment: &mut [Option<bool>]) -> bool {
+        let value = |assignment: &[Option<bool>], literal: i32| {
+            assignment[literal.unsigned_abs() as usize].map(|value| value == (literal > 0))
+        };
+        let mut trail = Vec::new();
+        let undo = |assignment: &mut [Option<bool>], trail: &[usize]| {
+            for variable in trail {
+                assignment[*variable] = None;
+            }
+        };
+
+        let branch = loop {
+            let mut unit = None;
+            let mut branch = None;
+            for clause in &self.clauses {
+                let mut free = clause
+                    .iter()
+                    .filter(|literal| value(assignment, **literal) != Some(false));
+                let Some(first) = free.next() else {
+                    undo(assignment, &trail);
+                    return false;
+                };
+                if clause
+                    .iter()
+                    .any(|literal| value(assignment, *literal) == Some(true))
+                {
+                    continue;
+                }
+                if free.next().is_none() {
+                    unit = Some(*first);
+                    break;
+                }
+                branch.get_or_insert(*first);
+            }
+
+            match unit {
+                Some(literal) => {
+                    let variable = literal.unsigned_abs() as usize;
+                    assignment[variable] = Some(literal > 0);
+                    trail.push(variable);
+                }
+                None => break branch,
+            }
+        };
+
+        let Some(literal) = branch else {
+            return true;
+        };
+        let variable = literal.unsigned_abs() as usize;
+        for choice in [literal > 0, literal < 0] {
+            assignment[variable] = Some(choice);
+            if self.search(assignment) {
+                return true;
+            }
+        }
+        assignment[variable] = None;
+        undo(assignment, &trail);
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A model of `expr`, if it can be true.
+    fn satisfy(expr: &str) -> Option<HashMap<String, bool>> {
+        let expr = Expr::parse(expr).unwrap();
+        let mut cnf = Cnf::default();
+        let output = cnf.encode(&expr);
+        cnf.add(vec![output]);
+        let model = cnf.solve()?;
+        assert!(expr.evaluate(&|name| model[name]));
+        Some(model)
+    }
+
+    #[test]
+    fn models_satisfy_the_expression() {
+        let model = satisfy("a & !b & (c ^ a)").unwrap();
+        assert_eq!((model["a"], model["b"], model["c"]), (true, false, false));
+        assert!(satisfy("(a | b) & (!a | c) & (!b | c) & !c").is_none());
+        assert!(satisfy("1").is_some());
+    }
+
+    #[test]
+    fn contradictions_are_unsatisfiable() {
+        assert!(satisfy("a & !a").is_none());
+        assert!(satisfy("0").is_none());
+        // Two ways of writing XOR never differ.
+        assert!(satisfy("(a ^ b) ^ (a & !b | !a & b)").is_none());
+    }
+
+    #[test]
+    fn pigeons_do_not_fit_in_fewer_holes() {
+        // Three pigeons, each in one of two holes, no hole shared.
+        let mut cnf = Cnf::default();
+        let pigeon = |pigeon: usize, hole: usize| Expr::Variable(format!("p{pigeon}h{hole}"));
+        for p in 0..3 {
+            let placed = Expr::Or(Box::new(pigeon(p, 0)), Box::new(pigeon(p, 1)));
+            let literal = cnf.encode(&placed);
+            cnf.add(vec![literal]);
+            for q in p + 1..3 {
+                for hole in 0..2 {
+                    let (a, b) = (cnf.encode(&pigeon(p, hole)), cnf.encode(&pigeon(q, hole)));
+                    cnf.add(vec![-a, -b]);
+                }
+            }
+        }
+        assert!(cnf.solve().is_none());
+    }
+}
@@ -0,0 +1,162 @@
+use bevy::prelude::*;
+
+use crate::{
+    analysis::{expression::Expr, minimize::minimize, network::Network},
+    console::{Console, ConsoleAppExt, ConsoleCommand},
+    cursor::CursorPosition,
+};
+
+/// Most variables a function may have to be synthesized.
+pub const MAX_VARIABLES: usize = 12;
+
+pub struct SynthesisPlugin;
+impl Plugin for SynthesisPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_console_command(
+            "synth",
+            "synth [name =] <expression>, place a sum of products computing e.g. \
+             `(A & B) | !C`, minimal up to 6 inputs",
+        )
+        .add_console_command(
+            "synthtable",
+            "synthtable <in1,in2,...> <outputs> [name], place a sum of products for a truth \
+             table column such as 0110 or 01-1, the first input being the most significant, \
+             minimal up to 6 inputs",
+        )
+        .add_systems(Update, synthesis_commands);
+    }
+}
+
+/// A single-output function given by its output on each row of its truth
+/// table, `None` meaning that the output does not matter.
+struct Function {
+    name: String,
+    inputs: Vec<String>,
+    rows: Vec<Option<bool>>,
+}
+
+impl Function {
+    fn from_expression(arguments: &str) -> Result<Function, String> {
+        let (name, expression) = match arguments.split_once('=') {
+            Some((name, expression)) => (name.trim(), expression),
+            None => ("out", arguments),
+        };
+        let expr = Expr::parse(expression)?;
+        let inputs = expr.variables();
+        check_variables(inputs.len())?;
+
+        let rows = (0..1usize << inputs.len())
+            .map(|row| {
+                let value = |variable: &str| {
+                    let index = inputs.iter().position(|input| input == variable);
+                    index.is_some_and(|index| row >> (inputs.len() - 1 - index) & 1 == 1)
+                };
+                Some(expr.evaluate(&value))
+            })
+            .collect();
+        Ok(Function {
+            name: name.to_string(),
+            inputs,
+            rows,
+        })
+    }
+
+    fn from_table(args: &[&str]) -> Result<Function, String> {
+        let (inputs, column, name) = match args {
+            [inputs, column] => (inputs, column, "out"),
+            [inputs, column, name] => (inputs, column, *name),
+            _ => return Err("usage: synthtable <in1,in2,...> <outputs> [name]".to_string()),
+        };
+        let inputs = inputs.split(',').map(str::to_string).collect::<Vec<_>>();
+        check_variables(inputs.len())?;
+        for (index, input) in inputs.iter().enumerate() {
+            if input.is_empty() {
+                return Err(format!("input {} has no name", index + 1));
+            }
+            if inputs[..index].contains(input) {
+                return Err(format!("input `{input}` is named twice"));
+            }
+        }
+        if column.len() != 1 << inputs.len() {
+            return Err(format!(
+                "{} inputs need {} outputs, not {}",
+                inputs.len(),
+                1 << inputs.len(),
+                column.len()
+            ));
+        }
+
+        let rows = column
+            .chars()
+            .map(|c| match c {
+                '0' => Ok(Some(false)),
+                '1' => Ok(Some(true)),
+                '-' | 'x' | 'X' => Ok(None),
+                c => Err(format!("`{c}` is not 0, 1 or -")),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Function {
+            name: name.to_string(),
+            inputs,
+            rows,
+        })
+    }
+
+    fn network(&self) -> Network {
+        let rows_at = |output: Option<bool>| {
+            (0..self.rows.len() as u32)
+                .filter(|row| self.rows[*row as usize] == output)
+                .collect::<Vec<_>>()
+        };
+        let terms = minimize(self.inputs.len(), &rows_at(Some(true)), &rows_at(None));
+
+        let mut network = Network::default();
+        for input in &self.inputs {
+            network.input(input);
+        }
+        network.add_sum_of_products(&self.name, &self.inputs, &terms);
+        network
+    }
+}
+
+fn check_variables(count: usize) -> Result<(), String> {
+    if count > MAX_VARIABLES {
+        return Err(format!("at most {MAX_VARIABLES} inputs can be synthesized"));
+    }
+    Ok(())
+}
+
+fn synthesis_commands(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    mut commands: Commands,
+    cursor: Res<CursorPosition>,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        let function = match command.name.as_str() {
+            "synth" => Function::from_expression(&command.arguments),
+            "synthtable" => Function::from_table(&command.args()),
+            _ => continue,
+        };
+
+        match function {
+            Ok(function) => function.network().spawn(&mut commands, cursor.in_world),
+            Err(error) => console.print(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables_need_distinct_input_names() {
+        let function = Function::from_table(&["a,b", "01-1", "y"]).unwrap();
+        assert_eq!(function.inputs, ["a", "b"]);
+        assert_eq!(function.rows, [Some(false), Some(true), None, Some(true)]);
+        assert!(Function::from_table(&["a,a", "0110"]).is_err());
+        assert!(Function::from_table(&["a,", "0110"]).is_err());
+        assert!(Function::from_table(&["a,b", "011"]).is_err());
+    }
+}
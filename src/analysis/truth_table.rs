@@ -0,0 +1,147 @@
+use std::fs;
+
+use bevy::prelude::*;
+
+use crate::{
+    analysis::SelectedPorts,
+    console::{Console, ConsoleAppExt, ConsoleCommand},
+    logic::headless::{Circuit, Snapshot},
+    signal::Signal,
+};
+
+/// Most inputs a truth table enumerates, i.e. 65536 rows.
+pub const MAX_INPUTS: usize = 16;
+
+pub struct TruthTablePlugin;
+impl Plugin for TruthTablePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TruthTable>()
+            .add_console_command(
+                "truthtable",
+                "truthtable [close], tabulate the selected outputs for every value of the selected buttons",
+            )
+            .add_console_command("ttcsv", "ttcsv <path>, export the truth table as CSV")
+            .add_systems(Update, truth_table_commands);
+    }
+}
+
+/// The last truth table computed, one row per combination of the inputs,
+/// the first input being the most significant.
+#[derive(Resource, Default)]
+pub struct TruthTable {
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+    /// The values of the inputs then of the outputs.
+    pub rows: Vec<Vec<Signal>>,
+    /// Whether the panel showing the table is open.
+    pub visible: bool,
+}
+
+impl TruthTable {
+    pub fn to_csv(&self) -> String {
+        let mut csv = self
+            .inputs
+            .iter()
+            .chain(&self.outputs)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(",");
+        csv.push('\n');
+        for row in &self.rows {
+            let cells = row.iter().map(Signal::to_string).collect::<Vec<_>>();
+            csv.push_str(&cells.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+fn truth_table_commands(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    mut table: ResMut<TruthTable>,
+    ports: SelectedPorts,
+    circuit: Circuit,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        match (command.name.as_str(), command.args().as_slice()) {
+            ("truthtable", ["close"]) => table.visible = false,
+            ("truthtable", []) => {
+                let (inputs, outputs) = ports.ports();
+                if inputs.is_empty() || outputs.is_empty() {
+                    console.print("select the buttons and the outputs to tabulate");
+                    continue;
+                }
+                if inputs.len() > MAX_INPUTS {
+                    console.print(format!("at most {MAX_INPUTS} inputs can be tabulated"));
+                    continue;
+                }
+
+                let sources = inputs.iter().map(|port| port.entity).collect::<Vec<_>>();
+                let sinks = outputs.iter().map(|port| port.entity).collect::<Vec<_>>();
+                let (rows, unsettled) = match tabulate(&mut circuit.snapshot(), &sources, &sinks) {
+                    Ok(table) => table,
+                    Err(error) => {
+                        console.print(error);
+                        continue;
+                    }
+                };
+
+                if unsettled > 0 {
+                    console.print(format!(
+                        "{unsettled} rows never settled, the circuit may oscillate"
+                    ));
+                }
+                *table = TruthTable {
+                    inputs: inputs.into_iter().map(|port| port.name).collect(),
+                    outputs: outputs.into_iter().map(|port| port.name).collect(),
+                    rows,
+                    visible: true,
+                };
+            }
+            ("truthtable", _) => console.print("usage: truthtable [close]"),
+            ("ttcsv", [path]) => {
+                if table.rows.is_empty() {
+                    console.print("no truth table to export, run `truthtable` first");
+                    continue;
+                }
+                match fs::write(path, table.to_csv()) {
+                    Ok(()) => console.print(format!("saved {} rows to `{path}`", table.rows.len())),
+                    Err(error) => console.print(format!("cannot write `{path}`: {error}")),
+                }
+            }
+            ("ttcsv", _) => console.print("usage: ttcsv <path>"),
+            _ => {}
+        }
+    }
+}
+
+/// Evaluates `outputs` for every value of `inputs`, returning the rows and
+/// how many of them never settled.
+fn tabulate(
+    snapshot: &mut Snapshot,
+    inputs: &[Entity],
+    outputs: &[Entity],
+) -> Result<(Vec<Vec<Signal>>, usize), String> {
+    let mut rows = Vec::new();
+    let mut unsettled = 0;
+    for combination in 0..1u64 << inputs.len() {
+        let sources = inputs
+            .iter()
+            .enumerate()
+            .map(|(index, input)| {
+                let bit = combination >> (inputs.len() - 1 - index) & 1;
+                (*input, Signal::bit(bit == 1))
+            })
+            .collect::<Vec<_>>();
+        unsettled += !snapshot.evaluate(&sources)? as usize;
+
+        let mut row = sources
+            .iter()
+            .map(|(_, signal)| *signal)
+            .collect::<Vec<_>>();
+        row.extend(outputs.iter().map(|output| snapshot.signal(*output)));
+        rows.push(row);
+    }
+    Ok((rows, unsettled))
+}
@@ -1,8 +1,9 @@
+use bevy::camera::visibility::RenderLayers;
 use bevy::input::mouse::AccumulatedMouseScroll;
 use bevy::prelude::*;
 use bevy::window::{CursorIcon, PrimaryWindow, SystemCursorIcon};
 
-use crate::cursor::CursorPosition;
+use crate::{cursor::CursorPosition, grid::GRID_LAYER};
 
 const ZOOM_SCROLL_SPEED: f32 = 0.1;
 const ZOOM_SCROLL_MAX: f32 = 1.;
@@ -39,6 +40,11 @@ struct CameraSettings {
     information: bool,
 }
 
+/// The camera showing the canvas in the window, as opposed to those
+/// rendering exports offscreen.
+#[derive(Component)]
+pub struct MainCamera;
+
 #[derive(Component)]
 struct CameraInformation;
 
@@ -49,6 +55,8 @@ struct CursorInformation;
 fn setup_camera(mut commands: Commands, camera_settings: Res<CameraSettings>) {
     commands.spawn((
         Camera2d,
+        MainCamera,
+        RenderLayers::from_layers(&[0, GRID_LAYER]),
         Projection::from(OrthographicProjection {
             scale: camera_settings.current_zoom,
             ..OrthographicProjection::default_2d()
@@ -82,7 +90,7 @@ fn setup_informations(mut commands: Commands, camera_settings: Res<CameraSetting
 
 /// Updates the camera / cursor position information displayed on the screen.
 fn update_information(
-    camera_query: Query<(&Transform, &Projection), With<Camera>>,
+    camera_query: Query<(&Transform, &Projection), With<MainCamera>>,
     mut camera_info_query: Query<&mut Text, (With<CameraInformation>, Without<CursorInformation>)>,
     mut cursor_info_query: Query<&mut Text, (With<CursorInformation>, Without<CameraInformation>)>,
     cursor_position: Res<CursorPosition>,
@@ -122,7 +130,7 @@ fn update_information(
 }
 
 fn zoom_camera(
-    q_camera: Single<(&mut Projection, &mut Transform), With<Camera>>,
+    q_camera: Single<(&mut Projection, &mut Transform), With<MainCamera>>,
     mouse_wheel_input: Res<AccumulatedMouseScroll>,
     mut camera_settings: ResMut<CameraSettings>,
     cursor: Res<CursorPosition>,
@@ -169,8 +177,9 @@ fn zoom_camera(
 }
 
 /// Moves the camera based on mouse drag input.
+#[allow(clippy::assign_op_pattern)]
 fn movement_camera(
-    mut camera: Single<&mut Transform, With<Camera>>,
+    mut camera: Single<&mut Transform, With<MainCamera>>,
     mut pick_position: Local<Option<Vec2>>,
     buttons: Res<ButtonInput<MouseButton>>,
     window: Single<(&Window, Entity), With<PrimaryWindow>>,
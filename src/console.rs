@@ -0,0 +1,223 @@
+use std::{collections::VecDeque, fmt::Display, ops::RangeInclusive, str::FromStr};
+
+use bevy::{
+    diagnostic::FrameCount,
+    input::{ButtonState, keyboard::KeyboardInput},
+    platform::collections::HashMap,
+    prelude::*,
+};
+
+/// Frames during which keyboard shortcuts stay disabled after the console
+/// closes, so the keys typed into it are not replayed as shortcuts.
+const CLOSE_COOLDOWN: u32 = 2;
+
+/// Lines of output shown under the prompt.
+const OUTPUT_LINES: usize = 8;
+
+/// A text prompt opened with Enter, used for everything that needs typed
+/// arguments (names, expressions, file paths, ...).
+pub struct ConsolePlugin;
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Console>()
+            .init_resource::<ConsoleCommands>()
+            .add_message::<ConsoleCommand>()
+            .add_console_command("help", "help [command], describe the available commands")
+            .add_systems(Startup, setup_console)
+            .add_systems(
+                Update,
+                (read_console_input, help_command, display_console).chain(),
+            );
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Console {
+    open: bool,
+    input: String,
+    /// The last lines printed.
+    output: VecDeque<String>,
+    closed_at: u32,
+}
+
+impl Console {
+    pub fn print(&mut self, line: impl Into<String>) {
+        self.output.push_back(line.into());
+        if self.output.len() > OUTPUT_LINES {
+            self.output.pop_front();
+        }
+    }
+}
+
+/// Names and help texts of the commands understood by the console.
+#[derive(Resource, Default)]
+pub struct ConsoleCommands(HashMap<String, String>);
+
+/// A command typed into the console, e.g. `tunnel CLK`.
+#[derive(Message, Clone)]
+pub struct ConsoleCommand {
+    pub name: String,
+    pub arguments: String,
+}
+
+impl ConsoleCommand {
+    pub fn args(&self) -> Vec<&str> {
+        self.arguments.split_whitespace().collect()
+    }
+
+    /// Parses argument `index` as a number within `range`, giving `default`
+    /// when it is missing.
+    pub fn number_arg<T>(
+        &self,
+        index: usize,
+        default: T,
+        range: RangeInclusive<T>,
+    ) -> Result<T, String>
+    where
+        T: FromStr + PartialOrd + Display,
+    {
+        let Some(arg) = self.args().get(index).copied() else {
+            return Ok(default);
+        };
+
+        match arg.parse::<T>() {
+            Ok(number) if range.contains(&number) => Ok(number),
+            _ => Err(format!(
+                "`{arg}` should be a number between {} and {}",
+                range.start(),
+                range.end()
+            )),
+        }
+    }
+}
+
+pub trait ConsoleAppExt {
+    fn add_console_command(&mut self, name: &str, help: &str) -> &mut Self;
+}
+
+impl ConsoleAppExt for App {
+    fn add_console_command(&mut self, name: &str, help: &str) -> &mut Self {
+        self.init_resource::<ConsoleCommands>();
+        self.world_mut()
+            .resource_mut::<ConsoleCommands>()
+            .0
+            .insert(name.to_string(), help.to_string());
+        self
+    }
+}
+
+/// Run condition for keyboard shortcuts, which must be ignored while typing.
+pub fn console_closed(console: Res<Console>, frame: Res<FrameCount>) -> bool {
+    !console.open && frame.0 > console.closed_at + CLOSE_COOLDOWN
+}
+
+#[derive(Component)]
+struct ConsoleText;
+
+fn setup_console(mut commands: Commands) {
+    commands.spawn((
+        ConsoleText,
+        Text::new(""),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(10.0),
+            bottom: Val::Px(10.0),
+            ..default()
+        },
+    ));
+}
+
+fn read_console_input(
+    mut inputs: MessageReader<KeyboardInput>,
+    mut console: ResMut<Console>,
+    mut commands_writer: MessageWriter<ConsoleCommand>,
+    known_commands: Res<ConsoleCommands>,
+    frame: Res<FrameCount>,
+) {
+    for input in inputs.read() {
+        if input.state != ButtonState::Pressed {
+            continue;
+        }
+
+        if !console.open {
+            if input.key_code == KeyCode::Enter {
+                console.open = true;
+                console.input.clear();
+            }
+            continue;
+        }
+
+        match input.key_code {
+            KeyCode::Escape => {
+                console.open = false;
+                console.closed_at = frame.0;
+            }
+            KeyCode::Enter => {
+                console.open = false;
+                console.closed_at = frame.0;
+
+                let line = std::mem::take(&mut console.input);
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+
+                let (name, arguments) = line.split_once(' ').unwrap_or((line, ""));
+                if !known_commands.0.contains_key(name) {
+                    console.print(format!("unknown command `{name}`, try `help`"));
+                    continue;
+                }
+
+                commands_writer.write(ConsoleCommand {
+                    name: name.to_string(),
+                    arguments: arguments.trim().to_string(),
+                });
+            }
+            KeyCode::Backspace => {
+                console.input.pop();
+            }
+            _ => {
+                if let Some(text) = &input.text {
+                    let text = text.chars().filter(|c| !c.is_control()).collect::<String>();
+                    console.input.push_str(&text);
+                }
+            }
+        }
+    }
+}
+
+fn help_command(
+    mut commands: MessageReader<ConsoleCommand>,
+    known_commands: Res<ConsoleCommands>,
+    mut console: ResMut<Console>,
+) {
+    for command in commands.read() {
+        if command.name != "help" {
+            continue;
+        }
+
+        if let Some(name) = command.args().first() {
+            match known_commands.0.get(*name) {
+                Some(help) => console.print(format!("{name}: {help}")),
+                None => console.print(format!("unknown command `{name}`")),
+            }
+            continue;
+        }
+
+        let mut names = known_commands.0.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        console.print(format!("commands: {}", names.join(", ")));
+    }
+}
+
+fn display_console(console: Res<Console>, mut text: Single<&mut Text, With<ConsoleText>>) {
+    if !console.is_changed() {
+        return;
+    }
+
+    let mut lines = console.output.iter().cloned().collect::<Vec<_>>();
+    if console.open {
+        lines.push(format!("> {}_", console.input));
+    }
+    text.0 = lines.join("\n");
+}
@@ -1,16 +1,135 @@
 use bevy::{input::keyboard::KeyboardInput, prelude::*};
 
 use crate::{
+    console::{Console, ConsoleAppExt, ConsoleCommand, console_closed},
     cursor,
-    logic::{Gate, Item},
+    link::InputAnchors,
+    logic::{
+        ButtonKind, Gate, Inputs, Item, Junction, LogicButton, Tunnel,
+        arithmetic::Arithmetic,
+        bus::{Merger, Splitter},
+        clock::Clock,
+        display::{DisplayKind, SegmentDisplay},
+        input_pin_position,
+        memory::{MAX_ADDRESS_WIDTH, Memory, MemoryKind},
+        plexer::{MAX_LINES, Plexer},
+        sequential::{Register, RegisterKind},
+        source::{Constant, Pull},
+        spawn_output_pins,
+    },
     selection::Selected,
+    signal::{MAX_WIDTH, Signal},
 };
 
 pub struct CreationPlugin;
 impl Plugin for CreationPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, handle_creation)
-            .add_systems(Update, connect_items);
+        app.add_console_command(
+            "tunnel",
+            "tunnel <name>, place a tunnel joined to every tunnel of that name",
+        )
+        .add_systems(Update, handle_creation.run_if(console_closed))
+        .add_console_command(
+            "splitter",
+            "splitter [bits], place a splitter breaking a bus into its bits",
+        )
+        .add_console_command(
+            "merger",
+            "merger [inputs], place a merger gathering its inputs into a bus",
+        )
+        .add_console_command("mux", "mux [inputs], place a multiplexer")
+        .add_console_command("demux", "demux [outputs], place a demultiplexer")
+        .add_console_command("encoder", "encoder [inputs], place a priority encoder")
+        .add_console_command("decoder", "decoder [outputs], place a binary decoder")
+        .add_console_command(
+            "adder",
+            "adder [bits], place an adder with carry in and out",
+        )
+        .add_console_command(
+            "subtractor",
+            "subtractor [bits], place a subtractor with borrow in and out",
+        )
+        .add_console_command(
+            "comparator",
+            "comparator [bits], place a magnitude comparator",
+        )
+        .add_console_command("multiplier", "multiplier [bits], place a multiplier")
+        .add_console_command(
+            "ram",
+            "ram [address bits] [data bits], place a RAM written on its clock rising edge",
+        )
+        .add_console_command("rom", "rom [address bits] [data bits], place a ROM")
+        .add_console_command(
+            "clock",
+            "clock, place a source following the simulation clock",
+        )
+        .add_console_command(
+            "register",
+            "register [bits], place a register loading its input on the clock rising edge",
+        )
+        .add_console_command(
+            "counter",
+            "counter [bits], place an up/down counter with reset",
+        )
+        .add_console_command(
+            "shifter",
+            "shifter [bits], place a shift register with serial in and out",
+        )
+        .add_console_command(
+            "pushbutton",
+            "pushbutton, place a button at 1 only while held",
+        )
+        .add_console_command(
+            "constant",
+            "constant <hex value> [bits], place a source of a fixed value",
+        )
+        .add_console_command("ground", "ground, place a constant 0")
+        .add_console_command("vcc", "vcc, place a constant 1")
+        .add_console_command(
+            "pullup",
+            "pullup, place a resistor setting the undriven bits of a net to 1",
+        )
+        .add_console_command(
+            "pulldown",
+            "pulldown, place a resistor setting the undriven bits of a net to 0",
+        )
+        .add_console_command(
+            "sevenseg",
+            "sevenseg, place a seven-segment display with one input per segment",
+        )
+        .add_console_command(
+            "hexdisplay",
+            "hexdisplay, place a display showing a 4 bits input as a hex digit",
+        )
+        .add_systems(
+            Update,
+            (
+                create_tunnel,
+                create_bus_components,
+                create_plexers,
+                create_arithmetic,
+                create_memories,
+                create_sequential,
+                create_displays,
+                create_sources,
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                connect_items::<Gate>,
+                connect_items::<Junction>,
+                connect_items::<Tunnel>,
+                connect_items::<Splitter>,
+                connect_items::<Merger>,
+                connect_items::<Plexer>,
+                connect_items::<Arithmetic>,
+                connect_items::<Memory>,
+                connect_items::<Register>,
+                connect_items::<SegmentDisplay>,
+            )
+                .run_if(console_closed),
+        );
     }
 }
 
@@ -27,7 +146,9 @@ fn handle_creation(
             KeyCode::KeyZ => commands.spawn(Gate::And(None, None)),
             KeyCode::KeyX => commands.spawn(Gate::Or(None, None)),
             KeyCode::KeyC => commands.spawn(Gate::Not(None)),
-            KeyCode::KeyV => commands.spawn(crate::logic::LogicButton),
+            KeyCode::KeyV => commands.spawn(LogicButton::default()),
+            KeyCode::KeyN => commands.spawn(Junction::default()),
+            KeyCode::KeyB => commands.spawn(Gate::TriState(None, None)),
             _ => continue,
         };
 
@@ -39,10 +160,306 @@ fn handle_creation(
     }
 }
 
-fn connect_items(
+fn create_tunnel(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    mut commands: Commands,
+    cursor: Res<cursor::CursorPosition>,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        if command.name != "tunnel" {
+            continue;
+        }
+
+        if command.arguments.is_empty() {
+            console.print("usage: tunnel <name>");
+            continue;
+        }
+
+        commands.spawn((
+            Tunnel {
+                name: command.arguments.clone(),
+                input: None,
+            },
+            Transform::from_translation(cursor.in_world.extend(0.0)),
+        ));
+    }
+}
+
+fn create_bus_components(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    mut commands: Commands,
+    cursor: Res<cursor::CursorPosition>,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        if command.name != "splitter" && command.name != "merger" {
+            continue;
+        }
+
+        let width = match command.number_arg(0, 8, 1..=MAX_WIDTH) {
+            Ok(width) => width,
+            Err(error) => {
+                console.print(error);
+                continue;
+            }
+        };
+
+        let transform = Transform::from_translation(cursor.in_world.extend(0.0));
+        if command.name == "splitter" {
+            let splitter = Splitter::new(width);
+            let size = splitter.size();
+            let mut entity = commands.spawn((splitter, transform));
+            spawn_output_pins(&mut entity, size, width as usize);
+        } else {
+            commands.spawn((Merger::new(width as usize), transform));
+        }
+    }
+}
+
+fn create_plexers(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    mut commands: Commands,
+    cursor: Res<cursor::CursorPosition>,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        let new_plexer: fn(usize) -> Plexer = match command.name.as_str() {
+            "mux" => Plexer::mux,
+            "demux" => Plexer::demux,
+            "encoder" => Plexer::encoder,
+            "decoder" => Plexer::decoder,
+            _ => continue,
+        };
+
+        let lines = match command.number_arg(0, 4, 2..=MAX_LINES) {
+            Ok(lines) => lines,
+            Err(error) => {
+                console.print(error);
+                continue;
+            }
+        };
+
+        let plexer = new_plexer(lines);
+        let size = plexer.size();
+        let pins = plexer.output_pins();
+        let mut entity = commands.spawn((
+            plexer,
+            Transform::from_translation(cursor.in_world.extend(0.0)),
+        ));
+        spawn_output_pins(&mut entity, size, pins);
+    }
+}
+
+fn create_arithmetic(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    mut commands: Commands,
+    cursor: Res<cursor::CursorPosition>,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        let new_component: fn(u8) -> Arithmetic = match command.name.as_str() {
+            "adder" => Arithmetic::adder,
+            "subtractor" => Arithmetic::subtractor,
+            "comparator" => Arithmetic::comparator,
+            "multiplier" => Arithmetic::multiplier,
+            _ => continue,
+        };
+
+        let width = match command.number_arg(0, 8, 1..=MAX_WIDTH) {
+            Ok(width) => width,
+            Err(error) => {
+                console.print(error);
+                continue;
+            }
+        };
+
+        let arithmetic = new_component(width);
+        let size = arithmetic.size();
+        let inputs = arithmetic.input_names().len();
+        let outputs = arithmetic.output_names().len();
+        let anchors = (0..inputs)
+            .map(|index| input_pin_position(size, index, inputs))
+            .collect();
+
+        let mut entity = commands.spawn((
+            arithmetic,
+            InputAnchors(anchors),
+            Transform::from_translation(cursor.in_world.extend(0.0)),
+        ));
+        spawn_output_pins(&mut entity, size, outputs);
+    }
+}
+
+fn create_memories(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    mut commands: Commands,
+    cursor: Res<cursor::CursorPosition>,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        let kind = match command.name.as_str() {
+            "ram" => MemoryKind::Ram,
+            "rom" => MemoryKind::Rom,
+            _ => continue,
+        };
+
+        let widths = command
+            .number_arg(0, 4, 1..=MAX_ADDRESS_WIDTH)
+            .and_then(|address| Ok((address, command.number_arg(1, 8, 1..=MAX_WIDTH)?)));
+        let (address_width, data_width) = match widths {
+            Ok(widths) => widths,
+            Err(error) => {
+                console.print(error);
+                continue;
+            }
+        };
+
+        let memory = Memory::new(kind, address_width, data_width);
+        let size = memory.size();
+        let inputs = memory.input_names().len();
+        let anchors = (0..inputs)
+            .map(|index| input_pin_position(size, index, inputs))
+            .collect();
+
+        let mut entity = commands.spawn((
+            memory,
+            InputAnchors(anchors),
+            Transform::from_translation(cursor.in_world.extend(0.0)),
+        ));
+        spawn_output_pins(&mut entity, size, 1);
+    }
+}
+
+fn create_sequential(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    mut commands: Commands,
+    cursor: Res<cursor::CursorPosition>,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        let transform = Transform::from_translation(cursor.in_world.extend(0.0));
+        let kind = match command.name.as_str() {
+            "clock" => {
+                commands.spawn((Clock, transform));
+                continue;
+            }
+            "pushbutton" => {
+                let button = LogicButton {
+                    kind: ButtonKind::Momentary,
+                    key: None,
+                };
+                commands.spawn((button, transform));
+                continue;
+            }
+            "register" => RegisterKind::Parallel,
+            "counter" => RegisterKind::Counter,
+            "shifter" => RegisterKind::Shift,
+            _ => continue,
+        };
+
+        let width = match command.number_arg(0, 8, 1..=MAX_WIDTH) {
+            Ok(width) => width,
+            Err(error) => {
+                console.print(error);
+                continue;
+            }
+        };
+
+        let register = Register::new(kind, width);
+        let size = register.size();
+        let inputs = register.input_names().len();
+        let outputs = register.output_names().len();
+        let anchors = (0..inputs)
+            .map(|index| input_pin_position(size, index, inputs))
+            .collect();
+
+        let mut entity = commands.spawn((register, InputAnchors(anchors), transform));
+        spawn_output_pins(&mut entity, size, outputs);
+    }
+}
+
+fn create_displays(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    mut commands: Commands,
+    cursor: Res<cursor::CursorPosition>,
+) {
+    for command in console_commands.read() {
+        let kind = match command.name.as_str() {
+            "sevenseg" => DisplayKind::SevenSegment,
+            "hexdisplay" => DisplayKind::Hex,
+            _ => continue,
+        };
+
+        let display = SegmentDisplay::new(kind);
+        let size = display.size();
+        let inputs = display.inputs.len();
+        let anchors = (0..inputs)
+            .map(|index| input_pin_position(size, index, inputs))
+            .collect();
+
+        commands.spawn((
+            display,
+            InputAnchors(anchors),
+            Transform::from_translation(cursor.in_world.extend(0.0)),
+        ));
+    }
+}
+
+fn create_sources(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    mut commands: Commands,
+    cursor: Res<cursor::CursorPosition>,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        let transform = Transform::from_translation(cursor.in_world.extend(0.0));
+        let constant = match command.name.as_str() {
+            "constant" => match constant_arg(command) {
+                Ok(signal) => Constant::Word(signal),
+                Err(error) => {
+                    console.print(error);
+                    continue;
+                }
+            },
+            "ground" => Constant::Ground,
+            "vcc" => Constant::Power,
+            "pullup" => {
+                commands.spawn((Pull::Up, transform));
+                continue;
+            }
+            "pulldown" => {
+                commands.spawn((Pull::Down, transform));
+                continue;
+            }
+            _ => continue,
+        };
+        commands.spawn((constant, transform));
+    }
+}
+
+/// Reads the hexadecimal value and the width of a `constant` command.
+fn constant_arg(command: &ConsoleCommand) -> Result<Signal, String> {
+    let Some(value) = command.args().first().copied() else {
+        return Err("usage: constant <hex value> [bits]".to_string());
+    };
+    let value = u64::from_str_radix(value.trim_start_matches("0x"), 16)
+        .map_err(|_| format!("invalid hexadecimal value `{value}`"))?;
+    let width = command.number_arg(1, 1, 1..=MAX_WIDTH)?;
+
+    let signal = Signal::new(width, value);
+    if signal.value() != Some(value) {
+        return Err(format!("{value:x} does not fit in {width} bits"));
+    }
+    Ok(signal)
+}
+
+/// Pressing L once remembers the selected item, pressing it again plugs that
+/// item into a free input of every selected `T`.
+fn connect_items<T: Inputs>(
     mut local_first: Local<Option<Entity>>,
-    query_frist: Query<Entity, (With<Item>, With<Selected>)>,
-    mut query_scd: Query<(&mut Gate, Entity), With<Selected>>,
+    query_first: Query<Entity, (With<Item>, With<Selected>)>,
+    mut query_scd: Query<(&mut T, Entity), With<Selected>>,
     mut inputs: MessageReader<KeyboardInput>,
 ) {
     for input in inputs.read() {
@@ -54,32 +471,15 @@ fn connect_items(
             continue;
         }
 
-        if let Some(first) = *local_first {
-            for (mut gate, second) in query_scd.iter_mut() {
+        if let Some(first) = local_first.take() {
+            for (mut component, second) in query_scd.iter_mut() {
                 if first == second {
                     continue;
                 }
-                match *gate {
-                    Gate::And(ref mut a, ref mut b) | Gate::Or(ref mut a, ref mut b) => {
-                        if a.is_none() {
-                            *a = Some(first);
-                        } else if b.is_none() {
-                            *b = Some(first);
-                        }
-                    }
-                    Gate::Not(ref mut a) => {
-                        if a.is_none() {
-                            *a = Some(first);
-                        }
-                    }
-                }
+                component.connect(first);
             }
-            *local_first = None;
         } else {
-            for first in query_frist.iter() {
-                *local_first = Some(first);
-                break;
-            }
+            *local_first = query_first.iter().next();
         }
     }
 }
@@ -1,5 +1,7 @@
 use bevy::{prelude::*, window::PrimaryWindow};
 
+use crate::camera::MainCamera;
+
 pub struct CursorPlugin;
 impl Plugin for CursorPlugin {
     fn build(&self, app: &mut App) {
@@ -17,9 +19,10 @@ pub struct CursorPosition {
     pub in_screen: Vec2,
 }
 
+#[allow(clippy::collapsible_if)]
 fn update_cursor_position(
     windows: Query<&Window, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &GlobalTransform), With<Camera>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
     mut cursor_position: ResMut<CursorPosition>,
 ) {
     let Ok(window) = windows.single() else {
@@ -0,0 +1,338 @@
+use bevy::{platform::collections::HashMap, prelude::*};
+
+use crate::{
+    camera::MainCamera,
+    console::{Console, ConsoleAppExt, ConsoleCommand},
+    logic::{
+        Item, ItemLabel, LogicButton, LogicSystems, Scheduler, SimulationTicks, Tunnel, Value,
+        clock::Clock,
+    },
+    selection::Selected,
+    signal::Signal,
+};
+
+/// Times an item must change value without any clock edge or button
+/// press to be reported as oscillating, far more than a change of input
+/// takes to settle.
+const OSCILLATION_CHANGES: u32 = 64;
+
+pub struct BreakpointPlugin;
+impl Plugin for BreakpointPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Breakpoints>()
+            .add_message::<BreakpointHit>()
+            .add_console_command(
+                "break",
+                "break [<label> ==|!= <value>|oscillation], pause when a condition becomes true, or list the breakpoints",
+            )
+            .add_console_command("unbreak", "unbreak <number|all>, remove breakpoints")
+            .add_systems(Update, breakpoint_commands)
+            .add_systems(
+                Update,
+                (check_breakpoints, stop_at_breakpoints)
+                    .chain()
+                    .after(LogicSystems),
+            );
+    }
+}
+
+enum Condition {
+    /// The item with the label, or the tunnels with the name, carry a value
+    /// equal or not to a number.
+    Compare {
+        name: String,
+        equal: bool,
+        number: u64,
+    },
+    /// Some item keeps changing value between two clock edges.
+    Oscillation,
+}
+
+struct Breakpoint {
+    number: usize,
+    text: String,
+    condition: Condition,
+    /// Whether the condition held at the previous tick, so that the
+    /// breakpoint only hits when it becomes true.
+    held: bool,
+}
+
+#[derive(Resource, Default)]
+pub struct Breakpoints {
+    list: Vec<Breakpoint>,
+    /// The number of the next breakpoint.
+    next: usize,
+}
+
+/// A breakpoint whose condition became true, because of `entity`.
+#[derive(Message)]
+pub struct BreakpointHit {
+    pub entity: Entity,
+    pub reason: String,
+}
+
+/// Reads a decimal number, or a hexadecimal or binary one with a `0x` or
+/// `0b` prefix.
+fn parse_number(text: &str) -> Option<u64> {
+    if let Some(digits) = text.strip_prefix("0x") {
+        u64::from_str_radix(digits, 16).ok()
+    } else if let Some(digits) = text.strip_prefix("0b") {
+        u64::from_str_radix(digits, 2).ok()
+    } else {
+        text.parse().ok()
+    }
+}
+
+fn parse_condition(args: &[&str]) -> Result<Condition, String> {
+    match args {
+        ["oscillation"] => Ok(Condition::Oscillation),
+        [name, operator @ ("==" | "!="), number] => Ok(Condition::Compare {
+            name: name.to_string(),
+            equal: *operator == "==",
+            number: parse_number(number).ok_or_else(|| format!("`{number}` is not a number"))?,
+        }),
+        _ => Err("usage: break <label> ==|!= <value>, or break oscillation".to_string()),
+    }
+}
+
+/// The items the breakpoints watch, with their values.
+type Items<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Value,
+        Option<&'static ItemLabel>,
+        Option<&'static Tunnel>,
+        Has<Clock>,
+        Has<LogicButton>,
+    ),
+    With<Item>,
+>;
+
+impl Condition {
+    /// The item named in the condition and whether the condition holds for
+    /// its value, an unknown value never being equal to a number.
+    fn compare(&self, items: &Items) -> Option<(Entity, bool)> {
+        let Condition::Compare {
+            name,
+            equal,
+            number,
+        } = self
+        else {
+            return None;
+        };
+        let (entity, value, ..) = items.iter().find(|(_, _, label, tunnel, ..)| {
+            label.is_some_and(|label| label.0 == *name)
+                || tunnel.is_some_and(|tunnel| tunnel.name == *name)
+        })?;
+        let matches = value.state.value() == Some(*number);
+        Some((entity, matches == *equal && value.state.value().is_some()))
+    }
+}
+
+fn breakpoint_commands(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    mut breakpoints: ResMut<Breakpoints>,
+    items: Items,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        let args = command.args();
+        match (command.name.as_str(), args.as_slice()) {
+            ("break", []) => {
+                if breakpoints.list.is_empty() {
+                    console.print("no breakpoint");
+                }
+                for breakpoint in &breakpoints.list {
+                    console.print(format!("{}: {}", breakpoint.number, breakpoint.text));
+                }
+            }
+            ("break", args) => match parse_condition(args) {
+                Ok(condition) => {
+                    let compared = condition.compare(&items);
+                    let held = compared.is_some_and(|(_, held)| held);
+                    if let Condition::Compare { name, .. } = &condition
+                        && compared.is_none()
+                    {
+                        console.print(format!("no item named {name} yet"));
+                    }
+                    breakpoints.next += 1;
+                    let number = breakpoints.next;
+                    console.print(format!("breakpoint {number}: {}", command.arguments));
+                    breakpoints.list.push(Breakpoint {
+                        number,
+                        text: command.arguments.clone(),
+                        condition,
+                        held,
+                    });
+                }
+                Err(error) => console.print(error),
+            },
+            ("unbreak", ["all"]) => breakpoints.list.clear(),
+            ("unbreak", [number]) => {
+                let count = breakpoints.list.len();
+                breakpoints
+                    .list
+                    .retain(|breakpoint| breakpoint.number.to_string() != *number);
+                if breakpoints.list.len() == count {
+                    console.print(format!("no breakpoint {number}"));
+                }
+            }
+            ("unbreak", _) => console.print("usage: unbreak <number|all>"),
+            _ => {}
+        }
+    }
+}
+
+/// How many times each item changed value since the last clock edge or
+/// button press. Clock edges are seen on the clock items themselves, so a
+/// circuit without any clock item can oscillate freely.
+#[derive(Default)]
+struct Oscillations {
+    changes: HashMap<Entity, (Signal, u32)>,
+}
+
+impl Oscillations {
+    /// Counts the changes of the items, given with their value and whether
+    /// they are a clock or a button, returning one that changed too many
+    /// times since the last clock edge or button press.
+    fn update(
+        &mut self,
+        items: impl IntoIterator<Item = (Entity, Signal, bool)>,
+    ) -> Option<Entity> {
+        let mut restart = false;
+        for (entity, state, is_source) in items {
+            let (previous, count) = self.changes.entry(entity).or_insert((state, 0));
+            if *previous != state {
+                *previous = state;
+                *count += 1;
+                restart |= is_source;
+            }
+        }
+
+        let oscillating = match restart {
+            true => None,
+            false => self
+                .changes
+                .iter()
+                .filter(|(_, (_, count))| *count > OSCILLATION_CHANGES)
+                .map(|(entity, _)| *entity)
+                .min(),
+        };
+        if restart || oscillating.is_some() {
+            self.changes.clear();
+        }
+        oscillating
+    }
+}
+
+fn check_breakpoints(
+    mut breakpoints: ResMut<Breakpoints>,
+    items: Items,
+    ticks: Res<SimulationTicks>,
+    mut checked: Local<u64>,
+    mut oscillations: Local<Oscillations>,
+    mut hits: MessageWriter<BreakpointHit>,
+) {
+    if *checked == ticks.0 {
+        return;
+    }
+    *checked = ticks.0;
+
+    let watches_oscillations = breakpoints
+        .list
+        .iter()
+        .any(|breakpoint| matches!(breakpoint.condition, Condition::Oscillation));
+    let oscillating = match watches_oscillations {
+        true => oscillations.update(items.iter().map(
+            |(entity, value, .., is_clock, is_button)| (entity, value.state, is_clock || is_button),
+        )),
+        false => None,
+    };
+
+    for breakpoint in breakpoints.list.iter_mut() {
+        let hit = match &breakpoint.condition {
+            Condition::Oscillation => oscillating,
+            condition => {
+                let Some((entity, held)) = condition.compare(&items) else {
+                    continue;
+                };
+                let became_true = held && !breakpoint.held;
+                breakpoint.held = held;
+                became_true.then_some(entity)
+            }
+        };
+        if let Some(entity) = hit {
+            hits.write(BreakpointHit {
+                entity,
+                reason: format!(
+                    "breakpoint {}: {} hit at tick {}",
+                    breakpoint.number, breakpoint.text, ticks.0
+                ),
+            });
+        }
+    }
+}
+
+/// Pauses the simulation on the item that hit a breakpoint, centering the
+/// camera on it and selecting it.
+fn stop_at_breakpoints(
+    mut hits: MessageReader<BreakpointHit>,
+    mut scheduler: ResMut<Scheduler>,
+    transforms: Query<&GlobalTransform>,
+    selected: Query<Entity, With<Selected>>,
+    mut camera: Single<&mut Transform, With<MainCamera>>,
+    mut commands: Commands,
+    mut console: ResMut<Console>,
+) {
+    let mut first = true;
+    for hit in hits.read() {
+        console.print(hit.reason.clone());
+        scheduler.paused = true;
+        scheduler.steps = 0;
+        if !first {
+            continue;
+        }
+        first = false;
+
+        if let Ok(transform) = transforms.get(hit.entity) {
+            let position = transform.translation();
+            camera.translation.x = position.x;
+            camera.translation.y = position.y;
+        }
+        for entity in selected.iter() {
+            commands.entity(entity).remove::<Selected>();
+        }
+        commands.entity(hit.entity).insert(Selected);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(index: u32) -> Entity {
+        Entity::from_raw_u32(index).unwrap()
+    }
+
+    #[test]
+    fn self_fed_inverter_oscillates() {
+        let inverter = entity(1);
+        let mut oscillations = Oscillations::default();
+        let hit = (0..=OSCILLATION_CHANGES + 1)
+            .find_map(|tick| oscillations.update([(inverter, Signal::bit(tick % 2 == 1), false)]));
+        assert_eq!(hit, Some(inverter));
+    }
+
+    #[test]
+    fn clock_edges_restart_the_count() {
+        let (clock, follower) = (entity(1), entity(2));
+        let mut oscillations = Oscillations::default();
+        for tick in 0..4 * OSCILLATION_CHANGES {
+            let level = Signal::bit(tick % 2 == 1);
+            let hit = oscillations.update([(clock, level, true), (follower, level, false)]);
+            assert_eq!(hit, None);
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use std::collections::VecDeque;
+
+use bevy::{ecs::system::SystemParam, platform::collections::HashMap, prelude::*};
+
+use crate::{
+    console::{Console, ConsoleAppExt, ConsoleCommand, console_closed},
+    logic::{
+        LogicSystems, Scheduler, SchedulerSystems, SimulationTicks, Value, simulation_running,
+    },
+    signal::Signal,
+};
+
+/// Ticks kept in the history.
+const MAX_HISTORY: usize = 1000;
+
+/// Keeps the values of the circuit at the last ticks, and shows them back
+/// on the canvas while the simulation is paused. Only the values on the
+/// outputs of the items are kept, not what registers and memories store.
+pub struct HistoryPlugin;
+impl Plugin for HistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<History>()
+            .add_console_command(
+                "rewind",
+                "rewind [ticks], show the values the circuit had a few ticks earlier, registers \
+                 and memories keeping their present contents",
+            )
+            .add_console_command("forward", "forward [ticks], move forward in the history")
+            .add_console_command("present", "present, leave the history")
+            .add_systems(
+                Update,
+                leave_history
+                    .run_if(simulation_running)
+                    .after(SchedulerSystems)
+                    .before(LogicSystems),
+            )
+            .add_systems(Update, record_history.after(LogicSystems))
+            .add_systems(
+                Update,
+                (history_commands, history_keys.run_if(console_closed)),
+            );
+    }
+}
+
+/// The values of the items that changed during a tick.
+struct Frame {
+    tick: u64,
+    changes: Vec<(Entity, Signal)>,
+}
+
+#[derive(Resource, Default)]
+pub struct History {
+    frames: VecDeque<Frame>,
+    /// The values of the items before the first frame, which the changes
+    /// of the frames are replayed over.
+    base: HashMap<Entity, Signal>,
+    /// The values of the items at the last frame.
+    last: HashMap<Entity, Signal>,
+    /// The frame shown, `None` for the present.
+    shown: Option<usize>,
+    /// The values of the items when the history was entered, put back when
+    /// leaving it.
+    present: HashMap<Entity, Signal>,
+    /// Whether the simulation was paused before entering the history.
+    was_paused: bool,
+}
+
+impl History {
+    /// The values of the items at frame `index`.
+    fn values_at(&self, index: usize) -> HashMap<Entity, Signal> {
+        let mut values = self.base.clone();
+        for frame in self.frames.range(..=index) {
+            values.extend(frame.changes.iter().copied());
+        }
+        values
+    }
+
+    /// Adds the frame of `tick` from the values of the items, forgetting
+    /// the items deleted since the last frame.
+    fn record(&mut self, tick: u64, values: HashMap<Entity, Signal>) {
+        let changes = values
+            .iter()
+            .filter(|(entity, state)| self.last.get(*entity) != Some(*state))
+            .map(|(entity, state)| (*entity, *state))
+            .collect();
+        self.frames.push_back(Frame { tick, changes });
+        if self.frames.len() > MAX_HISTORY
+            && let Some(first) = self.frames.pop_front()
+        {
+            self.base.extend(first.changes);
+        }
+        self.base.retain(|entity, _| values.contains_key(entity));
+        self.last = values;
+    }
+}
+
+/// Moves through the history, writing the values of the frame shown into
+/// the items. Entering the history pauses the simulation so that nothing
+/// is computed from these values.
+#[derive(SystemParam)]
+struct TimeTravel<'w, 's> {
+    history: ResMut<'w, History>,
+    scheduler: ResMut<'w, Scheduler>,
+    values: Query<'w, 's, (Entity, &'static mut Value)>,
+}
+
+impl TimeTravel<'_, '_> {
+    fn apply(&mut self, values: &HashMap<Entity, Signal>) {
+        for (entity, state) in values {
+            if let Ok((_, mut value)) = self.values.get_mut(*entity) {
+                value.state = *state;
+            }
+        }
+    }
+
+    /// Shows the frame `offset` ticks away from the one shown, and returns
+    /// a description of it.
+    fn travel(&mut self, offset: i64) -> Result<String, String> {
+        let Some(last) = self.history.frames.len().checked_sub(1) else {
+            return Err("no history yet".to_string());
+        };
+        let current = self.history.shown.unwrap_or(last);
+        let target = (current as i64 + offset).clamp(0, last as i64) as usize;
+        if target == last {
+            self.present();
+            return Ok("back to the present".to_string());
+        }
+
+        if self.history.shown.is_none() {
+            let present = self
+                .values
+                .iter()
+                .map(|(entity, value)| (entity, value.state))
+                .collect();
+            self.history.present = present;
+            self.history.was_paused = self.scheduler.paused;
+            self.scheduler.paused = true;
+        }
+        self.history.shown = Some(target);
+        let values = self.history.values_at(target);
+        self.apply(&values);
+
+        let tick = self.history.frames[target].tick;
+        Ok(format!("tick {tick}, {} ticks ago", last - target))
+    }
+
+    /// Puts back the values of the present, if the history is shown.
+    fn present(&mut self) {
+        if self.history.shown.take().is_none() {
+            return;
+        }
+        let present = std::mem::take(&mut self.history.present);
+        self.apply(&present);
+        self.scheduler.paused = self.history.was_paused;
+    }
+}
+
+/// Leaves the history when the simulation runs again, so that it goes on
+/// from the values of the present.
+fn leave_history(mut travel: TimeTravel) {
+    if travel.history.shown.is_none() {
+        return;
+    }
+    let (paused, steps) = (travel.scheduler.paused, travel.scheduler.steps);
+    travel.present();
+    travel.scheduler.paused = paused;
+    travel.scheduler.steps = steps;
+}
+
+fn record_history(
+    values: Query<(Entity, &Value)>,
+    ticks: Res<SimulationTicks>,
+    mut history: ResMut<History>,
+) {
+    if !ticks.is_changed() || history.shown.is_some() {
+        return;
+    }
+
+    let values = values
+        .iter()
+        .map(|(entity, value)| (entity, value.state))
+        .collect();
+    history.record(ticks.0, values);
+}
+
+fn history_commands(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    mut travel: TimeTravel,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        let direction = match command.name.as_str() {
+            "rewind" => -1,
+            "forward" => 1,
+            "present" => {
+                travel.present();
+                continue;
+            }
+            _ => continue,
+        };
+
+        let result = command
+            .number_arg(0, 1, 1..=MAX_HISTORY as i64)
+            .and_then(|ticks| travel.travel(direction * ticks));
+        match result {
+            Ok(message) | Err(message) => console.print(message),
+        }
+    }
+}
+
+/// Steps through the history with the left and right arrows.
+fn history_keys(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut travel: TimeTravel,
+    mut console: ResMut<Console>,
+) {
+    let offset = if keys.just_pressed(KeyCode::ArrowLeft) {
+        -1
+    } else if keys.just_pressed(KeyCode::ArrowRight) {
+        1
+    } else {
+        return;
+    };
+
+    match travel.travel(offset) {
+        Ok(message) | Err(message) => console.print(message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deleted_items_are_forgotten() {
+        let (kept, deleted) = (
+            Entity::from_raw_u32(1).unwrap(),
+            Entity::from_raw_u32(2).unwrap(),
+        );
+        let mut history = History::default();
+        for tick in 0..=MAX_HISTORY as u64 {
+            let bit = Signal::bit(tick % 2 == 1);
+            history.record(tick, HashMap::from_iter([(kept, bit), (deleted, bit)]));
+        }
+        assert_eq!(history.frames.len(), MAX_HISTORY);
+        assert!(history.base.contains_key(&deleted));
+        assert_eq!(history.values_at(0)[&kept], Signal::bit(true));
+
+        history.record(
+            MAX_HISTORY as u64 + 1,
+            HashMap::from_iter([(kept, Signal::bit(false))]),
+        );
+        assert!(!history.base.contains_key(&deleted));
+        assert!(!history.last.contains_key(&deleted));
+        assert_eq!(
+            history.values_at(MAX_HISTORY - 1)[&kept],
+            Signal::bit(false)
+        );
+    }
+}
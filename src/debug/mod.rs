@@ -0,0 +1,17 @@
+use bevy::prelude::*;
+
+use crate::debug::{breakpoint::BreakpointPlugin, history::HistoryPlugin, watch::WatchPlugin};
+
+pub mod breakpoint;
+pub mod history;
+pub mod watch;
+
+/// Tools following the values of a circuit while it is simulated.
+pub struct DebugPlugin;
+impl Plugin for DebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(WatchPlugin)
+            .add_plugins(BreakpointPlugin)
+            .add_plugins(HistoryPlugin);
+    }
+}
@@ -0,0 +1,160 @@
+use bevy::prelude::*;
+
+use crate::{
+    console::{Console, ConsoleAppExt, ConsoleCommand},
+    logic::{Item, ItemLabel, LogicSystems, SimulationTicks, Tunnel, Value},
+    selection::Selected,
+    signal::Signal,
+};
+
+pub struct WatchPlugin;
+impl Plugin for WatchPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_console_command(
+            "watch",
+            "watch [label ...], pin the selected or labelled items to the watch list",
+        )
+        .add_console_command(
+            "unwatch",
+            "unwatch [label ...|all], unpin the selected, labelled or all items",
+        )
+        .add_systems(Startup, setup_watch_list)
+        .add_systems(Update, watch_commands)
+        .add_systems(
+            Update,
+            (update_watches, display_watch_list)
+                .chain()
+                .after(LogicSystems),
+        );
+    }
+}
+
+/// An item pinned to the watch list, with a probe showing its value on the
+/// canvas.
+#[derive(Component)]
+pub struct Watched {
+    pub value: Signal,
+    /// The tick the value last changed at, `None` while it did not change
+    /// since the item was pinned.
+    pub changed_at: Option<u64>,
+}
+
+/// The name an item goes by in the watch list.
+fn watch_name(entity: Entity, label: Option<&ItemLabel>, tunnel: Option<&Tunnel>) -> String {
+    match (label, tunnel) {
+        (Some(label), _) => label.0.clone(),
+        (None, Some(tunnel)) => tunnel.name.clone(),
+        (None, None) => format!("n{}", entity.index()),
+    }
+}
+
+#[derive(Component)]
+struct WatchList;
+
+fn setup_watch_list(mut commands: Commands) {
+    commands.spawn((
+        WatchList,
+        Text::new(""),
+        TextFont::from_font_size(14.0),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(10.0),
+            top: Val::Px(10.0),
+            ..default()
+        },
+    ));
+}
+
+#[allow(clippy::type_complexity)]
+fn watch_commands(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    items: Query<(Entity, &Value, Option<&ItemLabel>, Has<Selected>), With<Item>>,
+    watched: Query<Entity, With<Watched>>,
+    mut commands: Commands,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        let args = command.args();
+        let targets = match (command.name.as_str(), args.as_slice()) {
+            ("unwatch", ["all"]) => {
+                for entity in watched.iter() {
+                    commands.entity(entity).remove::<Watched>();
+                }
+                continue;
+            }
+            ("watch" | "unwatch", []) => items
+                .iter()
+                .filter(|(.., selected)| *selected)
+                .map(|(entity, value, ..)| (entity, value))
+                .collect::<Vec<_>>(),
+            ("watch" | "unwatch", names) => {
+                let mut targets = Vec::new();
+                for name in names {
+                    let labelled = items
+                        .iter()
+                        .find(|(_, _, label, _)| label.is_some_and(|label| label.0 == *name));
+                    match labelled {
+                        Some((entity, value, ..)) => targets.push((entity, value)),
+                        None => console.print(format!("no item labelled {name}")),
+                    }
+                }
+                targets
+            }
+            _ => continue,
+        };
+
+        if targets.is_empty() {
+            console.print("no item to watch");
+        }
+        for (entity, value) in targets {
+            if command.name == "watch" {
+                commands.entity(entity).insert(Watched {
+                    value: value.state,
+                    changed_at: None,
+                });
+            } else {
+                commands.entity(entity).remove::<Watched>();
+            }
+        }
+    }
+}
+
+fn update_watches(mut watched: Query<(&mut Watched, &Value)>, ticks: Res<SimulationTicks>) {
+    // Values only change with the ticks, apart from those shown from the
+    // history.
+    if !ticks.is_changed() {
+        return;
+    }
+    for (mut watched, value) in watched.iter_mut() {
+        if watched.value != value.state {
+            watched.value = value.state;
+            watched.changed_at = Some(ticks.0);
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn display_watch_list(
+    watched: Query<(Entity, Ref<Watched>, Option<&ItemLabel>, Option<&Tunnel>)>,
+    mut removed: RemovedComponents<Watched>,
+    mut text: Single<&mut Text, With<WatchList>>,
+) {
+    let removed = removed.read().count() > 0;
+    let changed = watched.iter().any(|(_, watched, ..)| watched.is_changed());
+    if !changed && !removed {
+        return;
+    }
+
+    let mut lines = watched
+        .iter()
+        .map(|(entity, watched, label, tunnel)| {
+            let name = watch_name(entity, label, tunnel);
+            match watched.changed_at {
+                Some(tick) => format!("{name} = {}, changed at tick {tick}", watched.value),
+                None => format!("{name} = {}", watched.value),
+            }
+        })
+        .collect::<Vec<_>>();
+    lines.sort();
+    text.0 = lines.join("\n");
+}
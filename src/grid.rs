@@ -1,6 +1,10 @@
-use bevy::{prelude::*, window::PrimaryWindow};
+use bevy::{camera::visibility::RenderLayers, prelude::*, window::PrimaryWindow};
 
-const BASE_SPACING: f32 = 40.0;
+use crate::camera::MainCamera;
+
+pub const BASE_SPACING: f32 = 40.0;
+/// The render layer of the grid, which only the main camera shows.
+pub const GRID_LAYER: usize = 1;
 
 pub struct GridPlugin;
 impl Plugin for GridPlugin {
@@ -41,14 +45,16 @@ fn manage_grid_pool_size(
                 },
                 Transform::from_xyz(0.0, 0.0, -100.0),
                 Visibility::Hidden,
+                RenderLayers::layer(GRID_LAYER),
                 GridDot,
             ));
         }
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn update_grid_sprites(
-    camera_q: Single<(&Transform, &Projection), With<Camera>>,
+    camera_q: Single<(&Transform, &Projection), With<MainCamera>>,
     window_q: Single<&Window, With<PrimaryWindow>>,
     mut dots_q: Query<
         (&mut Transform, &mut Visibility, &mut Sprite),
@@ -1,11 +1,34 @@
 use bevy::{platform::collections::HashSet, prelude::*};
 
-use crate::logic::{Gate, Item};
+use crate::logic::{
+    Gate, Inputs, Item, Junction, Tunnel,
+    arithmetic::Arithmetic,
+    bus::{Merger, Splitter},
+    display::SegmentDisplay,
+    memory::Memory,
+    plexer::Plexer,
+    sequential::Register,
+};
 
 pub struct LinkPlugin;
 impl Plugin for LinkPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (link_system, suppr_links));
+        app.add_systems(
+            Update,
+            (
+                link_system::<Gate>,
+                link_system::<Junction>,
+                link_system::<Tunnel>,
+                link_system::<Splitter>,
+                link_system::<Merger>,
+                link_system::<Plexer>,
+                link_system::<Arithmetic>,
+                link_system::<Memory>,
+                link_system::<Register>,
+                link_system::<SegmentDisplay>,
+                suppr_links,
+            ),
+        );
     }
 }
 
@@ -13,35 +36,41 @@ impl Plugin for LinkPlugin {
 pub struct Link {
     pub from: Entity,
     pub to: Entity,
+    /// The input of `from` the link is plugged into.
+    pub input: usize,
     pub from_position: Vec2,
     pub to_position: Vec2,
 }
 
-fn link_system(
-    mut query: Query<(&Gate, Entity), Changed<Gate>>,
+/// Where the wires plugged into each input of an item end, relative to its
+/// center. Items without it take all their wires at their center.
+#[derive(Component)]
+pub struct InputAnchors(pub Vec<Vec2>);
+
+fn link_system<T: Inputs>(
+    query: Query<(&T, Entity), Changed<T>>,
+    links: Query<&Link>,
     mut commands: Commands,
-    mut local_links: Local<HashSet<(Entity, Entity)>>,
 ) {
-    for (gate, entity) in query.iter_mut() {
-        let links = match gate {
-            Gate::And(a, b) => vec![a, b],
-            Gate::Or(a, b) => vec![a, b],
-            Gate::Not(a) => vec![a],
-        };
-
-        for link in links {
-            if let Some(target_entity) = link {
-                let link_key = (entity, *target_entity);
-                if !local_links.contains(&link_key) {
-                    local_links.insert(link_key);
-
-                    commands.spawn(Link {
-                        from: entity,
-                        to: *target_entity,
-                        from_position: Vec2::ZERO,
-                        to_position: Vec2::ZERO,
-                    });
-                }
+    let mut existing: HashSet<(Entity, Entity, usize)> = links
+        .iter()
+        .map(|link| (link.from, link.to, link.input))
+        .collect();
+
+    for (component, entity) in query.iter() {
+        for (input, target_entity) in component.inputs().into_iter().enumerate() {
+            let Some(target_entity) = target_entity else {
+                continue;
+            };
+            let link_key = (entity, target_entity, input);
+            if existing.insert(link_key) {
+                commands.spawn(Link {
+                    from: entity,
+                    to: target_entity,
+                    input,
+                    from_position: Vec2::ZERO,
+                    to_position: Vec2::ZERO,
+                });
             }
         }
     }
@@ -0,0 +1,162 @@
+use bevy::prelude::*;
+
+use crate::{
+    logic::{Evaluate, Inputs, Item, PIN_SPACING},
+    selection::Moveable,
+};
+use ergo_core::{
+    arithmetic::{self, ArithmeticKind},
+    element::Element,
+};
+
+const ARITHMETIC_WIDTH: f32 = 40.0;
+
+/// An arithmetic item, holding the inputs of an [`arithmetic::Arithmetic`].
+#[derive(Component, Clone)]
+#[require(Item, Moveable)]
+pub enum Arithmetic {
+    Adder {
+        a: Option<Entity>,
+        b: Option<Entity>,
+        carry_in: Option<Entity>,
+        width: u8,
+    },
+    Subtractor {
+        a: Option<Entity>,
+        b: Option<Entity>,
+        borrow_in: Option<Entity>,
+        width: u8,
+    },
+    Comparator {
+        a: Option<Entity>,
+        b: Option<Entity>,
+        width: u8,
+    },
+    Multiplier {
+        a: Option<Entity>,
+        b: Option<Entity>,
+        width: u8,
+    },
+}
+
+impl Arithmetic {
+    pub fn adder(width: u8) -> Self {
+        Arithmetic::Adder {
+            a: None,
+            b: None,
+            carry_in: None,
+            width,
+        }
+    }
+
+    pub fn subtractor(width: u8) -> Self {
+        Arithmetic::Subtractor {
+            a: None,
+            b: None,
+            borrow_in: None,
+            width,
+        }
+    }
+
+    pub fn comparator(width: u8) -> Self {
+        Arithmetic::Comparator {
+            a: None,
+            b: None,
+            width,
+        }
+    }
+
+    pub fn multiplier(width: u8) -> Self {
+        Arithmetic::Multiplier {
+            a: None,
+            b: None,
+            width,
+        }
+    }
+
+    pub fn width(&self) -> u8 {
+        match self {
+            Arithmetic::Adder { width, .. }
+            | Arithmetic::Subtractor { width, .. }
+            | Arithmetic::Comparator { width, .. }
+            | Arithmetic::Multiplier { width, .. } => *width,
+        }
+    }
+
+    /// The symbol drawn on the component.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Arithmetic::Adder { .. } => "+",
+            Arithmetic::Subtractor { .. } => "-",
+            Arithmetic::Comparator { .. } => "<=>",
+            Arithmetic::Multiplier { .. } => "x",
+        }
+    }
+
+    pub fn input_names(&self) -> &'static [&'static str] {
+        match self {
+            Arithmetic::Adder { .. } => &["A", "B", "Cin"],
+            Arithmetic::Subtractor { .. } => &["A", "B", "Bin"],
+            Arithmetic::Comparator { .. } | Arithmetic::Multiplier { .. } => &["A", "B"],
+        }
+    }
+
+    pub fn output_names(&self) -> &'static [&'static str] {
+        match self {
+            Arithmetic::Adder { .. } => &["S", "Cout"],
+            Arithmetic::Subtractor { .. } => &["D", "Bout"],
+            Arithmetic::Comparator { .. } => &["<", "=", ">"],
+            Arithmetic::Multiplier { .. } => &["Lo", "Hi"],
+        }
+    }
+
+    pub fn size(&self) -> Vec2 {
+        let pins = self.input_names().len().max(self.output_names().len());
+        Vec2::new(ARITHMETIC_WIDTH, PIN_SPACING * (pins as f32 + 1.0))
+    }
+}
+
+impl Inputs for Arithmetic {
+    fn inputs(&self) -> Vec<Option<Entity>> {
+        match self {
+            Arithmetic::Adder {
+                a, b, carry_in: c, ..
+            }
+            | Arithmetic::Subtractor {
+                a, b, borrow_in: c, ..
+            } => vec![*a, *b, *c],
+            Arithmetic::Comparator { a, b, .. } | Arithmetic::Multiplier { a, b, .. } => {
+                vec![*a, *b]
+            }
+        }
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Option<Entity>> {
+        match self {
+            Arithmetic::Adder {
+                a, b, carry_in: c, ..
+            }
+            | Arithmetic::Subtractor {
+                a, b, borrow_in: c, ..
+            } => vec![a, b, c],
+            Arithmetic::Comparator { a, b, .. } | Arithmetic::Multiplier { a, b, .. } => {
+                vec![a, b]
+            }
+        }
+    }
+}
+
+impl Evaluate for Arithmetic {
+    fn element(&self) -> Element {
+        let kind = match self {
+            Arithmetic::Adder { .. } => ArithmeticKind::Adder,
+            Arithmetic::Subtractor { .. } => ArithmeticKind::Subtractor,
+            Arithmetic::Comparator { .. } => ArithmeticKind::Comparator,
+            Arithmetic::Multiplier { .. } => ArithmeticKind::Multiplier,
+        };
+        Element::Arithmetic(arithmetic::Arithmetic {
+            kind,
+            width: self.width(),
+        })
+    }
+}
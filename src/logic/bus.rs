@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+
+use crate::{
+    logic::{Evaluate, Inputs, Item, PIN_SPACING},
+    selection::Moveable,
+};
+use ergo_core::element::Element;
+
+/// Width of the body of the splitter and merger.
+const BUS_BODY_WIDTH: f32 = 16.0;
+
+fn body_size(pins: usize) -> Vec2 {
+    Vec2::new(BUS_BODY_WIDTH, PIN_SPACING * pins as f32)
+}
+
+/// A splitter item, see [`bus::split`](ergo_core::bus::split).
+#[derive(Component, Clone)]
+#[require(Item, Moveable)]
+pub struct Splitter {
+    pub input: Option<Entity>,
+    pub width: u8,
+}
+
+/// A merger item, see [`bus::merge`](ergo_core::bus::merge).
+#[derive(Component, Clone)]
+#[require(Item, Moveable)]
+pub struct Merger {
+    pub inputs: Vec<Option<Entity>>,
+}
+
+impl Splitter {
+    pub fn new(width: u8) -> Self {
+        Splitter { input: None, width }
+    }
+
+    pub fn size(&self) -> Vec2 {
+        body_size(self.width as usize)
+    }
+}
+
+impl Merger {
+    pub fn new(inputs: usize) -> Self {
+        Merger {
+            inputs: vec![None; inputs],
+        }
+    }
+
+    pub fn size(&self) -> Vec2 {
+        body_size(self.inputs.len())
+    }
+}
+
+impl Inputs for Splitter {
+    fn inputs(&self) -> Vec<Option<Entity>> {
+        vec![self.input]
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Option<Entity>> {
+        vec![&mut self.input]
+    }
+}
+
+impl Inputs for Merger {
+    fn inputs(&self) -> Vec<Option<Entity>> {
+        self.inputs.clone()
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Option<Entity>> {
+        self.inputs.iter_mut().collect()
+    }
+}
+
+impl Evaluate for Splitter {
+    fn element(&self) -> Element {
+        Element::Splitter(self.width)
+    }
+}
+
+impl Evaluate for Merger {
+    fn element(&self) -> Element {
+        Element::Merger
+    }
+}
@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+
+use crate::{
+    logic::{Item, Value},
+    selection::Moveable,
+    signal::Signal,
+};
+
+/// Fastest simulation clock, as the clock toggles at most once per frame.
+pub const MAX_FREQUENCY: f32 = 30.0;
+
+/// Side of the square body of a clock source.
+pub const CLOCK_SIZE: f32 = 20.0;
+
+/// The clock every `Clock` item follows. It toggles twice per period while
+/// running, and can be stepped by hand while paused.
+#[derive(Resource)]
+pub struct SimulationClock {
+    /// Full periods per second, 0 when paused.
+    pub frequency: f32,
+    pub level: bool,
+    /// Edges seen since the start, rising and falling.
+    pub edges: u64,
+    since_edge: f32,
+}
+
+impl Default for SimulationClock {
+    fn default() -> Self {
+        SimulationClock {
+            frequency: 1.0,
+            level: false,
+            edges: 0,
+            since_edge: 0.0,
+        }
+    }
+}
+
+impl SimulationClock {
+    /// Toggles the clock by hand.
+    pub fn step(&mut self) {
+        self.level = !self.level;
+        self.edges += 1;
+        self.since_edge = 0.0;
+    }
+}
+
+/// A source following the simulation clock.
+#[derive(Component)]
+#[require(Value, Item, Moveable)]
+pub struct Clock;
+
+pub fn run_simulation_clock(mut clock: ResMut<SimulationClock>, time: Res<Time>) {
+    if clock.frequency <= 0.0 {
+        return;
+    }
+
+    let half_period = 0.5 / clock.frequency;
+    clock.bypass_change_detection().since_edge += time.delta_secs();
+    if clock.since_edge >= half_period {
+        clock.step();
+    }
+}
+
+pub fn drive_clocks(
+    clock: Res<SimulationClock>,
+    mut clocks: Query<&mut Value, With<Clock>>,
+    new_clocks: Query<(), Added<Clock>>,
+) {
+    if !clock.is_changed() && new_clocks.is_empty() {
+        return;
+    }
+
+    for mut value in clocks.iter_mut() {
+        value.state = Signal::bit(clock.level);
+    }
+}
@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+
+use crate::{
+    logic::{Inputs, Item, Nets, PIN_SPACING, Value, input_state},
+    selection::Moveable,
+    signal::Level,
+};
+
+pub use ergo_core::display::DisplayKind;
+
+const DISPLAY_WIDTH: f32 = 40.0;
+const DISPLAY_HEIGHT: f32 = 60.0;
+
+/// An output item showing a digit on seven segments.
+#[derive(Component)]
+#[require(Item, Moveable, Segments)]
+pub struct SegmentDisplay {
+    pub kind: DisplayKind,
+    pub inputs: Vec<Option<Entity>>,
+}
+
+/// The state of the segments of a display, from `a` to `g`.
+#[derive(Component)]
+pub struct Segments(pub [Level; 7]);
+
+impl Default for Segments {
+    fn default() -> Self {
+        Segments([Level::HighZ; 7])
+    }
+}
+
+impl SegmentDisplay {
+    pub fn new(kind: DisplayKind) -> Self {
+        SegmentDisplay {
+            kind,
+            inputs: vec![None; kind.input_names().len()],
+        }
+    }
+
+    pub fn input_names(&self) -> &'static [&'static str] {
+        self.kind.input_names()
+    }
+
+    pub fn size(&self) -> Vec2 {
+        let pins = self.inputs.len() as f32 + 1.0;
+        Vec2::new(DISPLAY_WIDTH, DISPLAY_HEIGHT.max(PIN_SPACING * pins))
+    }
+}
+
+impl Inputs for SegmentDisplay {
+    fn inputs(&self) -> Vec<Option<Entity>> {
+        self.inputs.clone()
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Option<Entity>> {
+        self.inputs.iter_mut().collect()
+    }
+}
+
+pub fn update_displays(
+    mut displays: Query<(&SegmentDisplay, &mut Segments)>,
+    values: Query<&Value>,
+    nets: Nets,
+) {
+    for (display, mut segments) in displays.iter_mut() {
+        let inputs = display
+            .inputs
+            .iter()
+            .map(|input| input_state(*input, &values, &nets))
+            .collect::<Vec<_>>();
+        let lit = display.kind.segments(&inputs);
+        if segments.0 != lit {
+            segments.0 = lit;
+        }
+    }
+}
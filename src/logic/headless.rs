@@ -0,0 +1,153 @@
+use bevy::{ecs::system::SystemParam, platform::collections::HashMap, prelude::*};
+use ergo_core::{
+    element::Element,
+    simulation::{self, Part, PartId, Simulator},
+};
+
+use crate::{
+    logic::{
+        Evaluate, Gate, Item, Junction, OutputPin, Tunnel, Value,
+        arithmetic::Arithmetic,
+        bus::{Merger, Splitter},
+        memory::Memory,
+        plexer::Plexer,
+        sequential::Register,
+        source::{Constant, Pull},
+    },
+    signal::Signal,
+};
+
+/// A copy of the circuit that can be evaluated many times at once, instead
+/// of propagating signals one frame at a time through the ECS. Registers and
+/// memories act as sources holding their current contents, their inputs
+/// other than the address of a memory being left unplugged so that they
+/// never see a clock edge.
+pub struct Snapshot {
+    simulator: Simulator,
+    /// The part standing for each item.
+    parts: HashMap<Entity, PartId>,
+    /// The values of the parts when the snapshot was taken.
+    values: Vec<Signal>,
+}
+
+impl Snapshot {
+    /// The value of the net `entity` belongs to.
+    pub fn signal(&self, entity: Entity) -> Signal {
+        match self.parts.get(&entity) {
+            Some(id) => self.simulator.value(*id),
+            None => Signal::undefined(1),
+        }
+    }
+
+    /// Evaluates the circuit with the items of `sources` forced to the given
+    /// values, starting from the values it had when taken. Returns whether
+    /// the values stopped changing.
+    pub fn evaluate(&mut self, sources: &[(Entity, Signal)]) -> Result<bool, String> {
+        self.simulator.restore(&self.values);
+        for (entity, signal) in sources {
+            let id = self
+                .parts
+                .get(entity)
+                .ok_or_else(|| format!("{entity} is not part of the circuit"))?;
+            self.simulator.set(*id, *signal)?;
+        }
+        Ok(self.simulator.settle())
+    }
+}
+
+/// Takes snapshots of the circuit.
+#[derive(SystemParam)]
+pub struct Circuit<'w, 's> {
+    items: Query<'w, 's, (Entity, Option<&'static Value>), With<Item>>,
+    pins: Query<'w, 's, (&'static OutputPin, &'static ChildOf)>,
+    junctions: Query<'w, 's, &'static Junction>,
+    tunnels: Query<'w, 's, &'static Tunnel>,
+    constants: Query<'w, 's, &'static Constant>,
+    pulls: Query<'w, 's, &'static Pull>,
+    gates: Query<'w, 's, &'static Gate>,
+    splitters: Query<'w, 's, &'static Splitter>,
+    mergers: Query<'w, 's, &'static Merger>,
+    plexers: Query<'w, 's, &'static Plexer>,
+    arithmetic: Query<'w, 's, &'static Arithmetic>,
+    memories: Query<'w, 's, &'static Memory>,
+    registers: Query<'w, 's, &'static Register>,
+}
+
+impl Circuit<'_, '_> {
+    /// Converts the items into the parts of a headless circuit. Items which
+    /// do not compute anything, like buttons and clocks, become inputs.
+    pub fn snapshot(&self) -> Snapshot {
+        let items = self.items.iter().collect::<Vec<_>>();
+        let parts = items
+            .iter()
+            .enumerate()
+            .map(|(index, (item, _))| (*item, PartId(index)))
+            .collect::<HashMap<_, _>>();
+        let input = |input: Option<Entity>| input.and_then(|input| parts.get(&input).copied());
+
+        let mut circuit = simulation::Circuit::default();
+        for &(item, value) in &items {
+            let part = if let Ok((pin, parent)) = self.pins.get(item)
+                && let Some(parent) = parts.get(&parent.parent())
+            {
+                Part::Pin(*parent, pin.0)
+            } else if let Ok(junction) = self.junctions.get(item) {
+                Part::Junction(input(junction.0))
+            } else if let Ok(tunnel) = self.tunnels.get(item) {
+                Part::Tunnel(tunnel.name.clone(), input(tunnel.input))
+            } else if let Ok(pull) = self.pulls.get(item) {
+                Part::Pull(pull.level())
+            } else if let Ok(constant) = self.constants.get(item) {
+                Part::Constant(constant.signal())
+            } else if let Some((element, inputs)) = self.element(item) {
+                Part::Component(element, inputs.into_iter().map(input).collect())
+            } else {
+                Part::Input(value.map_or(Signal::undefined(1), |value| value.state))
+            };
+            circuit.add(part);
+        }
+
+        let mut simulator = Simulator::new(circuit);
+        let mut values = simulator.values().to_vec();
+        for (index, (_, value)) in items.iter().enumerate() {
+            if let Some(value) = value {
+                values[index] = value.state;
+            }
+        }
+        simulator.restore(&values);
+
+        Snapshot {
+            simulator,
+            parts,
+            values,
+        }
+    }
+
+    /// The element computing the outputs of a component, and its inputs.
+    /// Registers and memories only keep the inputs they are read through.
+    fn element(&self, item: Entity) -> Option<(Element, Vec<Option<Entity>>)> {
+        fn convert<T: Evaluate>(component: &T) -> (Element, Vec<Option<Entity>>) {
+            (component.element(), component.inputs())
+        }
+
+        self.gates
+            .get(item)
+            .map(convert)
+            .or_else(|_| self.splitters.get(item).map(convert))
+            .or_else(|_| self.mergers.get(item).map(convert))
+            .or_else(|_| self.plexers.get(item).map(convert))
+            .or_else(|_| self.arithmetic.get(item).map(convert))
+            .or_else(|_| {
+                self.memories.get(item).map(|memory| {
+                    let address = memory.inputs.iter().take(1).copied().collect();
+                    (memory.element(), address)
+                })
+            })
+            .or_else(|_| {
+                self.registers
+                    .get(item)
+                    .map(|register| (register.element(), vec![]))
+            })
+            .ok()
+    }
+}
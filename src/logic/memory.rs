@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+
+use crate::{
+    logic::{Evaluate, Inputs, Item, Nets, PIN_SPACING, Value, input_state},
+    selection::Moveable,
+    signal::Signal,
+};
+use ergo_core::{element::Element, memory};
+
+pub use ergo_core::memory::{MAX_ADDRESS_WIDTH, MemoryKind, words_from_hex};
+
+const MEMORY_WIDTH: f32 = 50.0;
+
+/// A memory item, holding a [`memory::Memory`] and its inputs.
+#[derive(Component, Clone, Deref, DerefMut)]
+#[require(Item, Moveable)]
+pub struct Memory {
+    #[deref]
+    pub memory: memory::Memory,
+    pub inputs: Vec<Option<Entity>>,
+}
+
+impl Memory {
+    pub fn new(kind: MemoryKind, address_width: u8, data_width: u8) -> Self {
+        let memory = memory::Memory::new(kind, address_width, data_width);
+        Memory {
+            inputs: vec![None; memory.input_names().len()],
+            memory,
+        }
+    }
+
+    pub fn size(&self) -> Vec2 {
+        Vec2::new(MEMORY_WIDTH, PIN_SPACING * 5.0)
+    }
+}
+
+impl Inputs for Memory {
+    fn inputs(&self) -> Vec<Option<Entity>> {
+        self.inputs.clone()
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Option<Entity>> {
+        self.inputs.iter_mut().collect()
+    }
+}
+
+impl Evaluate for Memory {
+    fn element(&self) -> Element {
+        Element::Memory(self.memory.clone())
+    }
+
+    /// Reads the word without copying the contents.
+    fn evaluate(&self, inputs: &[Signal]) -> Vec<Signal> {
+        vec![self.read(inputs)]
+    }
+}
+
+/// Stores the data input of the RAMs seeing a rising edge on their clock.
+pub fn write_memories(mut memories: Query<&mut Memory>, values: Query<&Value>, nets: Nets) {
+    for mut memory in memories.iter_mut() {
+        let inputs = memory
+            .inputs
+            .iter()
+            .map(|input| input_state(*input, &values, &nets))
+            .collect::<Vec<_>>();
+        if memory.bypass_change_detection().clock(&inputs) {
+            memory.set_changed();
+        }
+    }
+}
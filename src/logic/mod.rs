@@ -0,0 +1,431 @@
+use crate::{console::Console, selection::Moveable, signal::Signal};
+use bevy::{
+    ecs::{component::Mutable, system::SystemParam},
+    platform::collections::HashSet,
+    prelude::*,
+};
+use ergo_core::{
+    element::{Element, Operation},
+    simulation::{Node, Wiring, resolve_net},
+};
+
+pub mod arithmetic;
+pub mod bus;
+pub mod clock;
+pub mod display;
+pub mod headless;
+pub mod memory;
+pub mod plexer;
+pub mod sequential;
+pub mod source;
+
+use arithmetic::Arithmetic;
+use bus::{Merger, Splitter};
+use clock::{SimulationClock, drive_clocks, run_simulation_clock};
+use display::{SegmentDisplay, update_displays};
+use memory::{Memory, write_memories};
+use plexer::Plexer;
+use sequential::{Register, clock_registers};
+use source::{Pull, drive_constants};
+
+/// Distance between two pins along the edge of a component.
+pub const PIN_SPACING: f32 = 12.0;
+pub const PIN_RADIUS: f32 = 4.0;
+/// Radius of the knob operating a button, at its center.
+pub const KNOB_RADIUS: f32 = 5.0;
+
+pub struct LogicPlugin;
+impl Plugin for LogicPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SimulationClock>()
+            .init_resource::<SimulationTicks>()
+            .init_resource::<Scheduler>()
+            .configure_sets(Update, LogicSystems.run_if(simulation_running))
+            .configure_sets(Update, SchedulerSystems.before(LogicSystems))
+            .add_systems(
+                Update,
+                (
+                    (
+                        prune_inputs::<Gate>,
+                        prune_inputs::<Junction>,
+                        prune_inputs::<Tunnel>,
+                        prune_inputs::<Splitter>,
+                        prune_inputs::<Merger>,
+                        prune_inputs::<Plexer>,
+                        prune_inputs::<Arithmetic>,
+                        prune_inputs::<Memory>,
+                        prune_inputs::<Register>,
+                        prune_inputs::<SegmentDisplay>,
+                    ),
+                    (run_simulation_clock, drive_clocks).chain(),
+                    drive_constants,
+                    write_memories,
+                    clock_registers,
+                    (
+                        update_logic_system::<Gate>,
+                        update_logic_system::<Splitter>,
+                        update_logic_system::<Merger>,
+                        update_logic_system::<Plexer>,
+                        update_logic_system::<Arithmetic>,
+                        update_logic_system::<Memory>,
+                        update_logic_system::<Register>,
+                        update_net_system::<Junction>,
+                        update_net_system::<Tunnel>,
+                    ),
+                    update_displays,
+                    report_contention,
+                    count_ticks,
+                )
+                    .chain()
+                    .in_set(LogicSystems),
+            );
+    }
+}
+
+/// The systems computing the values of the circuit.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LogicSystems;
+
+/// The systems pausing, resuming or stepping the simulation, which run
+/// before it.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SchedulerSystems;
+
+/// Passes of the logic systems since the start, each moving signals one
+/// item further.
+#[derive(Resource, Default)]
+pub struct SimulationTicks(pub u64);
+
+/// Whether the logic systems run. Pausing them freezes every value, until
+/// the simulation resumes or is stepped.
+#[derive(Resource, Default)]
+pub struct Scheduler {
+    pub paused: bool,
+    /// Ticks left to run while paused.
+    pub steps: u64,
+}
+
+pub fn simulation_running(scheduler: Res<Scheduler>) -> bool {
+    !scheduler.paused || scheduler.steps > 0
+}
+
+fn count_ticks(mut ticks: ResMut<SimulationTicks>, mut scheduler: ResMut<Scheduler>) {
+    ticks.0 += 1;
+    if scheduler.steps > 0 {
+        scheduler.steps -= 1;
+    }
+}
+
+#[derive(Component, Default)]
+pub struct Item;
+
+/// A gate item, holding the inputs of an [`Operation`].
+#[derive(Component, Clone)]
+#[require(Item, Moveable)]
+pub enum Gate {
+    And(Option<Entity>, Option<Entity>),
+    Or(Option<Entity>, Option<Entity>),
+    Not(Option<Entity>),
+    TriState(Option<Entity>, Option<Entity>),
+}
+
+/// A junction item, see [`Part::Junction`](ergo_core::simulation::Part::Junction).
+#[derive(Component, Default)]
+#[require(Item, Moveable)]
+pub struct Junction(pub Option<Entity>);
+
+/// A tunnel item, see [`Part::Tunnel`](ergo_core::simulation::Part::Tunnel).
+#[derive(Component)]
+#[require(Item, Moveable)]
+pub struct Tunnel {
+    pub name: String,
+    pub input: Option<Entity>,
+}
+
+/// An output of a component producing several signals, spawned as a child of
+/// that component. The number is the index of the output.
+#[derive(Component)]
+#[require(Item)]
+pub struct OutputPin(pub usize);
+
+/// A name given to an item, used to refer to it in analyses and exports.
+#[derive(Component)]
+pub struct ItemLabel(pub String);
+
+#[derive(Component, Default)]
+pub struct Value {
+    pub state: Signal,
+}
+
+/// Items whose inputs reference other items.
+pub trait Inputs: Component<Mutability = Mutable> {
+    fn inputs(&self) -> Vec<Option<Entity>>;
+    fn inputs_mut(&mut self) -> Vec<&mut Option<Entity>>;
+
+    /// Plugs `source` into the first free input, returning whether one was found.
+    fn connect(&mut self, source: Entity) -> bool {
+        match self.inputs_mut().into_iter().find(|input| input.is_none()) {
+            Some(input) => {
+                *input = Some(source);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Unplugs every input fed by `source`.
+    fn disconnect(&mut self, source: Entity) {
+        for input in self.inputs_mut() {
+            if *input == Some(source) {
+                *input = None;
+            }
+        }
+    }
+}
+
+impl Inputs for Gate {
+    fn inputs(&self) -> Vec<Option<Entity>> {
+        match self {
+            Gate::And(a, b) | Gate::Or(a, b) | Gate::TriState(a, b) => vec![*a, *b],
+            Gate::Not(a) => vec![*a],
+        }
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Option<Entity>> {
+        match self {
+            Gate::And(a, b) | Gate::Or(a, b) | Gate::TriState(a, b) => vec![a, b],
+            Gate::Not(a) => vec![a],
+        }
+    }
+}
+
+impl Inputs for Junction {
+    fn inputs(&self) -> Vec<Option<Entity>> {
+        vec![self.0]
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Option<Entity>> {
+        vec![&mut self.0]
+    }
+}
+
+impl Inputs for Tunnel {
+    fn inputs(&self) -> Vec<Option<Entity>> {
+        vec![self.input]
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Option<Entity>> {
+        vec![&mut self.input]
+    }
+}
+
+/// Items computing their outputs from the signals on their inputs.
+pub trait Evaluate: Inputs {
+    /// The element of a headless circuit computing the same outputs.
+    fn element(&self) -> Element;
+
+    /// Gets one signal per input, unconnected ones floating. Components with
+    /// output pins return one value per pin, the others a single value.
+    fn evaluate(&self, inputs: &[Signal]) -> Vec<Signal> {
+        self.element().evaluate(inputs)
+    }
+}
+
+/// The wiring items which only carry a signal without computing anything.
+#[derive(SystemParam)]
+pub struct Nets<'w, 's> {
+    junctions: Query<'w, 's, &'static Junction>,
+    tunnels: Query<'w, 's, &'static Tunnel>,
+    pulls: Query<'w, 's, &'static Pull>,
+}
+
+impl Wiring for Nets<'_, '_> {
+    type Id = Entity;
+
+    fn node(&self, entity: Entity) -> Node<'_, Entity> {
+        if let Ok(junction) = self.junctions.get(entity) {
+            Node::Junction(junction.0)
+        } else if let Ok(tunnel) = self.tunnels.get(entity) {
+            Node::Tunnel(&tunnel.name)
+        } else if let Ok(pull) = self.pulls.get(entity) {
+            Node::Pull(pull.level())
+        } else {
+            Node::Driver
+        }
+    }
+
+    fn tunnel_inputs(&self, name: &str) -> Vec<Entity> {
+        self.tunnels
+            .iter()
+            .filter(|tunnel| tunnel.name == name)
+            .filter_map(|tunnel| tunnel.input)
+            .collect()
+    }
+}
+
+impl Nets<'_, '_> {
+    /// The value of the net `entity` belongs to, and whether its drivers
+    /// force some bit to opposite values.
+    pub fn resolve(&self, entity: Entity, values: &Query<&Value>) -> (Signal, bool) {
+        let (drivers, pull) = self.pulled_drivers(entity);
+        let signals = drivers.into_iter().map(|driver| {
+            values
+                .get(driver)
+                .map_or(Signal::undefined(1), |value| value.state)
+        });
+        resolve_net(signals.collect(), pull)
+    }
+}
+
+pub(crate) fn input_state(input: Option<Entity>, values: &Query<&Value>, nets: &Nets) -> Signal {
+    let Some(input) = input else {
+        return Signal::floating(1);
+    };
+
+    nets.resolve(input, values).0
+}
+
+/// Position of output pin `index` out of `count`, on the right edge of a
+/// component body of the given size.
+pub fn output_pin_position(size: Vec2, index: usize, count: usize) -> Vec2 {
+    let top = (count as f32 - 1.0) * PIN_SPACING / 2.0;
+    Vec2::new(size.x / 2.0 + PIN_RADIUS, top - index as f32 * PIN_SPACING)
+}
+
+/// Position of input `index` out of `count`, on the left edge of a component
+/// body of the given size.
+pub fn input_pin_position(size: Vec2, index: usize, count: usize) -> Vec2 {
+    let top = (count as f32 - 1.0) * PIN_SPACING / 2.0;
+    Vec2::new(-size.x / 2.0, top - index as f32 * PIN_SPACING)
+}
+
+/// Spawns `count` output pins along the right edge of a component body.
+pub fn spawn_output_pins(entity: &mut EntityCommands, size: Vec2, count: usize) {
+    entity.with_children(|parent| {
+        for index in 0..count {
+            let position = output_pin_position(size, index, count);
+            parent.spawn((
+                OutputPin(index),
+                Transform::from_translation(position.extend(0.1)),
+            ));
+        }
+    });
+}
+
+impl Gate {
+    pub fn operation(&self) -> Operation {
+        match self {
+            Gate::And(_, _) => Operation::And,
+            Gate::Or(_, _) => Operation::Or,
+            Gate::Not(_) => Operation::Not,
+            Gate::TriState(_, _) => Operation::TriState,
+        }
+    }
+}
+
+impl Evaluate for Gate {
+    fn element(&self) -> Element {
+        Element::Gate(self.operation())
+    }
+}
+
+/// Drops input references to items that no longer exist.
+pub fn prune_inputs<T: Inputs>(mut query: Query<&mut T>, items: Query<(), With<Item>>) {
+    for mut component in query.iter_mut() {
+        let dangling = component
+            .inputs()
+            .into_iter()
+            .flatten()
+            .filter(|input| !items.contains(*input))
+            .collect::<Vec<_>>();
+
+        for input in dangling {
+            component.disconnect(input);
+        }
+    }
+}
+
+pub fn update_logic_system<T: Evaluate>(
+    query: Query<(Entity, &T, Option<&Children>)>,
+    values: Query<&Value>,
+    pins: Query<&OutputPin>,
+    nets: Nets,
+    mut commands: Commands,
+) {
+    for (entity, component, children) in query.iter() {
+        let inputs = component
+            .inputs()
+            .into_iter()
+            .map(|input| input_state(input, &values, &nets))
+            .collect::<Vec<_>>();
+        let outputs = component.evaluate(&inputs);
+
+        let output_pins = children
+            .into_iter()
+            .flat_map(|children| children.iter())
+            .filter_map(|child| Some((child, pins.get(child).ok()?.0)))
+            .collect::<Vec<_>>();
+
+        let output = |index: usize| outputs.get(index).copied().unwrap_or(Signal::undefined(1));
+
+        if output_pins.is_empty() {
+            commands.entity(entity).insert(Value { state: output(0) });
+        }
+        for (pin, index) in output_pins {
+            commands.entity(pin).insert(Value {
+                state: output(index),
+            });
+        }
+    }
+}
+
+/// Marks the wiring items of a net whose drivers force opposite values.
+#[derive(Component)]
+pub struct Contention;
+
+/// Colors wiring items after the value of the net they belong to, flagging
+/// the nets driven to opposite values.
+fn update_net_system<T: Component>(
+    query: Query<(Entity, Has<Contention>), With<T>>,
+    values: Query<&Value>,
+    nets: Nets,
+    mut commands: Commands,
+) {
+    for (entity, had_contention) in query.iter() {
+        let (state, contention) = nets.resolve(entity, &values);
+        commands.entity(entity).insert(Value { state });
+        if contention && !had_contention {
+            commands.entity(entity).insert(Contention);
+        } else if !contention && had_contention {
+            commands.entity(entity).remove::<Contention>();
+        }
+    }
+}
+
+fn report_contention(query: Query<&Tunnel, Added<Contention>>, mut console: ResMut<Console>) {
+    let names = query
+        .iter()
+        .map(|tunnel| tunnel.name.as_str())
+        .collect::<HashSet<_>>();
+    for name in names {
+        console.print(format!("bus contention on net `{name}`"));
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum ButtonKind {
+    /// Flips its value each time it is pressed.
+    #[default]
+    Toggle,
+    /// At 1 only while held down.
+    Momentary,
+}
+
+/// An input set by hand, by clicking its knob or pressing the key it is
+/// bound to.
+#[derive(Component, Default)]
+#[require(Value, Item, Moveable)]
+pub struct LogicButton {
+    pub kind: ButtonKind,
+    pub key: Option<KeyCode>,
+}
@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+
+use crate::{
+    logic::{Evaluate, Inputs, Item, PIN_SPACING},
+    selection::Moveable,
+};
+use ergo_core::{element::Element, plexer};
+
+pub use ergo_core::plexer::MAX_LINES;
+
+const PLEXER_WIDTH: f32 = 30.0;
+
+/// A routing item, holding the inputs of a [`plexer::Plexer`].
+#[derive(Component, Clone)]
+#[require(Item, Moveable)]
+pub enum Plexer {
+    Mux {
+        inputs: Vec<Option<Entity>>,
+        select: Option<Entity>,
+    },
+    Demux {
+        input: Option<Entity>,
+        select: Option<Entity>,
+        outputs: usize,
+    },
+    Encoder {
+        inputs: Vec<Option<Entity>>,
+    },
+    Decoder {
+        select: Option<Entity>,
+        outputs: usize,
+    },
+}
+
+impl Plexer {
+    pub fn mux(lines: usize) -> Self {
+        Plexer::Mux {
+            inputs: vec![None; lines],
+            select: None,
+        }
+    }
+
+    pub fn demux(lines: usize) -> Self {
+        Plexer::Demux {
+            input: None,
+            select: None,
+            outputs: lines,
+        }
+    }
+
+    pub fn encoder(lines: usize) -> Self {
+        Plexer::Encoder {
+            inputs: vec![None; lines],
+        }
+    }
+
+    pub fn decoder(lines: usize) -> Self {
+        Plexer::Decoder {
+            select: None,
+            outputs: lines,
+        }
+    }
+
+    /// Number of output pins, 0 when the component holds its single output.
+    pub fn output_pins(&self) -> usize {
+        match self {
+            Plexer::Mux { .. } => 0,
+            Plexer::Demux { outputs, .. } | Plexer::Decoder { outputs, .. } => *outputs,
+            Plexer::Encoder { .. } => 2,
+        }
+    }
+
+    /// Heights of the left (input) and right (output) sides of the trapezoid.
+    pub fn side_heights(&self) -> (f32, f32) {
+        let wide = |lines: usize| PIN_SPACING * (lines as f32 + 1.0);
+        let narrow = PIN_SPACING * 2.0;
+
+        match self {
+            Plexer::Mux { inputs, .. } | Plexer::Encoder { inputs } => (wide(inputs.len()), narrow),
+            Plexer::Demux { outputs, .. } | Plexer::Decoder { outputs, .. } => {
+                (narrow, wide(*outputs))
+            }
+        }
+    }
+
+    pub fn size(&self) -> Vec2 {
+        let (left, right) = self.side_heights();
+        Vec2::new(PLEXER_WIDTH, left.max(right))
+    }
+}
+
+impl Inputs for Plexer {
+    fn inputs(&self) -> Vec<Option<Entity>> {
+        match self {
+            Plexer::Mux { inputs, select } => inputs.iter().copied().chain([*select]).collect(),
+            Plexer::Demux { input, select, .. } => vec![*input, *select],
+            Plexer::Encoder { inputs } => inputs.clone(),
+            Plexer::Decoder { select, .. } => vec![*select],
+        }
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Option<Entity>> {
+        match self {
+            Plexer::Mux { inputs, select } => inputs.iter_mut().chain([select]).collect(),
+            Plexer::Demux { input, select, .. } => vec![input, select],
+            Plexer::Encoder { inputs } => inputs.iter_mut().collect(),
+            Plexer::Decoder { select, .. } => vec![select],
+        }
+    }
+}
+
+impl Evaluate for Plexer {
+    fn element(&self) -> Element {
+        Element::Plexer(match self {
+            Plexer::Mux { .. } => plexer::Plexer::Mux,
+            Plexer::Demux { outputs, .. } => plexer::Plexer::Demux { outputs: *outputs },
+            Plexer::Encoder { inputs } => plexer::Plexer::Encoder {
+                lines: inputs.len(),
+            },
+            Plexer::Decoder { outputs, .. } => plexer::Plexer::Decoder { outputs: *outputs },
+        })
+    }
+}
@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+
+use crate::{
+    logic::{Evaluate, Inputs, Item, Nets, PIN_SPACING, Value, input_state},
+    selection::Moveable,
+};
+use ergo_core::{element::Element, sequential};
+
+pub use ergo_core::sequential::RegisterKind;
+
+const REGISTER_WIDTH: f32 = 40.0;
+
+/// A register item, holding a [`sequential::Register`] and its inputs.
+#[derive(Component, Clone, Deref, DerefMut)]
+#[require(Item, Moveable)]
+pub struct Register {
+    #[deref]
+    pub register: sequential::Register,
+    pub inputs: Vec<Option<Entity>>,
+}
+
+impl Register {
+    pub fn new(kind: RegisterKind, width: u8) -> Self {
+        let register = sequential::Register::new(kind, width);
+        Register {
+            inputs: vec![None; register.input_names().len()],
+            register,
+        }
+    }
+
+    pub fn size(&self) -> Vec2 {
+        let pins = self.input_names().len().max(self.output_names().len());
+        Vec2::new(REGISTER_WIDTH, PIN_SPACING * (pins as f32 + 1.0))
+    }
+}
+
+impl Inputs for Register {
+    fn inputs(&self) -> Vec<Option<Entity>> {
+        self.inputs.clone()
+    }
+
+    fn inputs_mut(&mut self) -> Vec<&mut Option<Entity>> {
+        self.inputs.iter_mut().collect()
+    }
+}
+
+impl Evaluate for Register {
+    fn element(&self) -> Element {
+        Element::Register(self.register.clone())
+    }
+}
+
+/// Updates the registers seeing a rising edge on their clock, and clears the
+/// counters being reset.
+pub fn clock_registers(mut registers: Query<&mut Register>, values: Query<&Value>, nets: Nets) {
+    for mut register in registers.iter_mut() {
+        let inputs = register
+            .inputs
+            .iter()
+            .map(|input| input_state(*input, &values, &nets))
+            .collect::<Vec<_>>();
+        if register.bypass_change_detection().clock(&inputs) {
+            register.set_changed();
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+
+use crate::{
+    logic::{Item, Value},
+    selection::Moveable,
+    signal::{Level, Signal},
+};
+
+/// A source whose value never changes.
+#[derive(Component, Clone, Copy)]
+#[require(Value, Item, Moveable)]
+pub enum Constant {
+    Word(Signal),
+    /// A single bit at 0.
+    Ground,
+    /// A single bit at 1.
+    Power,
+}
+
+impl Constant {
+    pub fn signal(&self) -> Signal {
+        match self {
+            Constant::Word(signal) => *signal,
+            Constant::Ground => Signal::bit(false),
+            Constant::Power => Signal::bit(true),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Constant::Word(signal) => {
+                let digits = signal.width().div_ceil(4) as usize;
+                format!("{:0digits$x}", signal.value().unwrap_or_default())
+            }
+            Constant::Ground => "GND".to_string(),
+            Constant::Power => "VCC".to_string(),
+        }
+    }
+
+    pub fn size(&self) -> Vec2 {
+        let characters = self.label().len() as f32;
+        Vec2::new((characters * 6.0 + 8.0).max(20.0), 20.0)
+    }
+}
+
+/// A resistor giving its level to the bits of a net nothing else drives.
+/// It is wired like a source, into a junction, tunnel or input.
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+#[require(Item, Moveable)]
+pub enum Pull {
+    Up,
+    Down,
+}
+
+impl Pull {
+    pub const SIZE: Vec2 = Vec2::new(24.0, 12.0);
+
+    pub fn level(&self) -> Level {
+        match self {
+            Pull::Up => Level::High,
+            Pull::Down => Level::Low,
+        }
+    }
+}
+
+pub fn drive_constants(mut constants: Query<(&Constant, &mut Value), Changed<Constant>>) {
+    for (constant, mut value) in constants.iter_mut() {
+        value.state = constant.signal();
+    }
+}
@@ -1,25 +1,33 @@
 mod action;
+mod analysis;
 mod camera;
+mod console;
 mod creation;
 mod cursor;
+mod debug;
 mod grid;
 mod link;
 mod logic;
+mod netlist;
 mod renderer;
 pub mod selection;
+mod waveform;
 
 use bevy::prelude::*;
+use ergo_core::signal;
 
 use crate::{
-    action::ActionPlugin, camera::CameraPlugin, creation::CreationPlugin, cursor::CursorPlugin,
-    grid::GridPlugin, link::LinkPlugin, logic::LogicPlugin, renderer::RendererPlugin,
-    selection::SelectionPlugin,
+    action::ActionPlugin, analysis::AnalysisPlugin, camera::CameraPlugin, console::ConsolePlugin,
+    creation::CreationPlugin, cursor::CursorPlugin, debug::DebugPlugin, grid::GridPlugin,
+    link::LinkPlugin, logic::LogicPlugin, netlist::NetlistPlugin, renderer::RendererPlugin,
+    selection::SelectionPlugin, waveform::WaveformPlugin,
 };
 
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
         .add_plugins(CameraPlugin)
+        .add_plugins(ConsolePlugin)
         .add_plugins(CursorPlugin)
         .add_plugins(GridPlugin)
         .add_plugins(LogicPlugin)
@@ -28,5 +36,9 @@ fn main() {
         .add_plugins(ActionPlugin)
         .add_plugins(CreationPlugin)
         .add_plugins(LinkPlugin)
+        .add_plugins(AnalysisPlugin)
+        .add_plugins(NetlistPlugin)
+        .add_plugins(WaveformPlugin)
+        .add_plugins(DebugPlugin)
         .run();
 }
@@ -0,0 +1,166 @@
+use crate::{analysis::expression::Expr, netlist::Netlist};
+
+/// The logic function of a `.names` block: the OR of its cube lines, which
+/// give the output value for inputs matching their `0`, `1` and `-`.
+fn cover(inputs: &[&str], cubes: &[(String, char)]) -> Result<Expr, String> {
+    let mut on_set = None;
+    let mut terms = Vec::new();
+    for (cube, output) in cubes {
+        if cube.len() != inputs.len() {
+            return Err(format!(
+                "cube `{cube}` does not have {} inputs",
+                inputs.len()
+            ));
+        }
+        if *on_set.get_or_insert(*output) != *output {
+            return Err("a cover mixes on-set and off-set cubes".to_string());
+        }
+
+        let literals = cube
+            .chars()
+            .zip(inputs)
+            .filter_map(|(value, input)| {
+                let variable = Expr::Variable(input.to_string());
+                match value {
+                    '1' => Some(Ok(variable)),
+                    '0' => Some(Ok(Expr::Not(Box::new(variable)))),
+                    '-' => None,
+                    c => Some(Err(format!("`{c}` is not 0, 1 or -"))),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let product = literals
+            .into_iter()
+            .reduce(|a, b| Expr::And(Box::new(a), Box::new(b)))
+            .unwrap_or(Expr::Constant(true));
+        terms.push(product);
+    }
+
+    let sum = terms
+        .into_iter()
+        .reduce(|a, b| Expr::Or(Box::new(a), Box::new(b)))
+        .unwrap_or(Expr::Constant(false));
+    Ok(match on_set {
+        Some('0') => Expr::Not(Box::new(sum)),
+        Some('1') | None => sum,
+        Some(c) => return Err(format!("`{c}` is not an output value")),
+    })
+}
+
+/// The signals of a `.names` block, the output last, and its cube lines with
+/// their output value.
+type Block<'a> = (Vec<&'a str>, Vec<(String, char)>);
+
+/// Reads the combinational part of the Berkeley Logic Interchange Format:
+/// `.inputs`, `.outputs` and `.names` covers of a single model.
+pub fn parse(text: &str) -> Result<Netlist, String> {
+    let text = text.replace("\\\n", " ");
+    let mut netlist = Netlist::default();
+    // The `.names` block being read.
+    let mut names: Option<Block> = None;
+
+    let lines = text
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty());
+    for line in lines {
+        let mut words = line.split_whitespace();
+        let Some(keyword) = words.next() else {
+            continue;
+        };
+
+        if !keyword.starts_with('.') {
+            let Some((_, cubes)) = names.as_mut() else {
+                return Err(format!("`{line}` is outside of a .names block"));
+            };
+            let cube = match (words.next(), words.next()) {
+                (Some(output), None) => (keyword.to_string(), output),
+                (None, None) => (String::new(), keyword),
+                _ => return Err(format!("`{line}` is not a cube")),
+            };
+            let mut output = cube.1.chars();
+            let (Some(value), None) = (output.next(), output.next()) else {
+                return Err(format!("`{line}` is not a cube"));
+            };
+            cubes.push((cube.0, value));
+            continue;
+        }
+
+        if let Some((signals, cubes)) = names.take()
+            && let Some((output, inputs)) = signals.split_last()
+        {
+            netlist.define(output, cover(inputs, &cubes)?)?;
+        }
+        match keyword {
+            ".model" | ".end" => {}
+            ".inputs" => netlist.inputs.extend(words.map(str::to_string)),
+            ".outputs" => netlist.outputs.extend(words.map(str::to_string)),
+            ".names" => {
+                let signals = words.collect::<Vec<_>>();
+                if signals.is_empty() {
+                    return Err(".names needs an output".to_string());
+                }
+                names = Some((signals, Vec::new()));
+            }
+            keyword => return Err(format!("{keyword} is not supported")),
+        }
+    }
+
+    if let Some((signals, cubes)) = names
+        && let Some((output, inputs)) = signals.split_last()
+    {
+        netlist.define(output, cover(inputs, &cubes)?)?;
+    }
+    Ok(netlist)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn covers_or_their_cubes() {
+        let netlist = parse(
+            ".model m # a comment\n.inputs a b \\\n c\n.outputs y\n\
+             .names a b t\n11 1\n.names t c y\n1- 1\n-1 1\n.end\n",
+        )
+        .unwrap();
+        assert_eq!(netlist.inputs, ["a", "b", "c"]);
+        assert_eq!(netlist.outputs, ["y"]);
+        for row in 0..8 {
+            let (a, b, c) = (row & 4 != 0, row & 2 != 0, row & 1 != 0);
+            let inputs = [("a", a), ("b", b), ("c", c)];
+            assert_eq!(netlist.evaluate("y", &inputs), a && b || c);
+        }
+    }
+
+    #[test]
+    fn off_set_covers_are_inverted() {
+        let netlist = parse(".inputs a b\n.outputs y\n.names a b y\n11 0\n").unwrap();
+        assert!(!netlist.evaluate("y", &[("a", true), ("b", true)]));
+        assert!(netlist.evaluate("y", &[("a", false), ("b", true)]));
+    }
+
+    #[test]
+    fn constants_have_no_inputs() {
+        let netlist = parse(".outputs one zero\n.names one\n1\n.names zero\n").unwrap();
+        assert!(netlist.evaluate("one", &[]));
+        assert!(!netlist.evaluate("zero", &[]));
+    }
+
+    #[test]
+    fn malformed_covers_are_rejected() {
+        for text in [
+            "11 1\n",
+            ".names a y\n11 1\n",
+            ".names a b y\n11 1\n00 0\n",
+            ".names a y\n2 1\n",
+            ".names a y\n1 1 1\n",
+            ".names\n",
+            ".latch a b\n",
+            ".names a y\n1 1\n.names a y\n0 1\n",
+        ] {
+            assert!(parse(text).is_err(), "{text:?}");
+        }
+    }
+}
@@ -0,0 +1,844 @@
+use bevy::{platform::collections::HashMap, prelude::*};
+
+use crate::{
+    logic::{
+        ButtonKind, Gate, ItemLabel, Junction, LogicButton, Tunnel,
+        clock::Clock,
+        source::{Constant, Pull},
+    },
+    netlist::xml::{self, Element},
+    signal::Signal,
+};
+
+/// Size of a Logisim grid step relative to ours.
+const SCALE: f32 = 1.2;
+/// Offset of the gates added to build a Logisim gate we have no
+/// counterpart for, from the gate they feed.
+const HELPER_OFFSET: Vec2 = Vec2::new(-40.0, -16.0);
+
+/// Most inputs a Logisim gate takes.
+const MAX_GATE_INPUTS: i32 = 32;
+/// Widest bus Logisim has.
+const MAX_WIDTH: u8 = 32;
+
+/// Subcircuits nested deeper than this are taken to include themselves.
+const MAX_DEPTH: usize = 16;
+
+/// A location on the Logisim canvas, y pointing down.
+type Point = (i32, i32);
+
+/// A location in one of the circuits placed, told apart by the order they
+/// were placed in, as a subcircuit may be placed several times.
+type Spot = (usize, Point);
+
+fn point(text: &str) -> Option<Point> {
+    let (x, y) = text
+        .trim()
+        .strip_prefix('(')?
+        .strip_suffix(')')?
+        .split_once(',')?;
+    Some((x.trim().parse().ok()?, y.trim().parse().ok()?))
+}
+
+/// Turns an offset from a component facing east into one from the same
+/// component facing `facing`.
+fn rotate((dx, dy): Point, facing: &str) -> Point {
+    match facing {
+        "west" => (-dx, -dy),
+        "north" => (dy, -dx),
+        "south" => (-dy, dx),
+        _ => (dx, dy),
+    }
+}
+
+/// Offset of input `index` from the output of a gate facing east, after
+/// Logisim's `AbstractGate` spreading of the inputs over its height.
+fn gate_input(size: i32, inputs: i32, index: i32) -> Point {
+    let (start, distance, lower_even) = match inputs {
+        0..=3 if size < 40 => (-5, 10, 10),
+        0..=3 if size < 60 || inputs <= 2 => (-10, 20, 20),
+        0..=3 => (-15, 30, 30),
+        4 if size >= 60 => (-5, 20, 0),
+        _ => (-5, 10, 10),
+    };
+    let dy = if inputs % 2 == 1 {
+        start * (inputs - 1) + distance * index
+    } else {
+        start * inputs + distance * index + if index >= inputs / 2 { lower_even } else { 0 }
+    };
+    (-size, dy)
+}
+
+#[derive(Clone, Copy)]
+enum Operation {
+    And,
+    Or,
+    Xor,
+}
+
+/// What a Logisim component becomes once its inputs are known.
+enum Build {
+    /// A gate over any number of inputs, each possibly negated, built
+    /// from two-input gates.
+    Logic {
+        operation: Operation,
+        inverted: bool,
+        negated: Vec<bool>,
+    },
+    TriState,
+    /// An output pin, LED or probe, shown by a junction.
+    Output,
+    Tunnel(String),
+    /// A source, complete from the start.
+    Source,
+}
+
+struct Part {
+    /// The circuit placed the part belongs to.
+    scope: usize,
+    entity: Entity,
+    position: Vec2,
+    output: Option<Point>,
+    inputs: Vec<Point>,
+    build: Build,
+}
+
+/// The points joined by wires.
+#[derive(Default)]
+struct Connections {
+    parent: HashMap<Spot, Spot>,
+}
+
+impl Connections {
+    fn find(&mut self, point: Spot) -> Spot {
+        let parent = *self.parent.entry(point).or_insert(point);
+        if parent == point {
+            return point;
+        }
+        let root = self.find(parent);
+        self.parent.insert(point, root);
+        root
+    }
+
+    fn join(&mut self, a: Spot, b: Spot) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a != b {
+            self.parent.insert(a, b);
+        }
+    }
+}
+
+/// Whether `point` lies on the horizontal or vertical wire from `a` to `b`.
+fn on_wire(point: Point, (a, b): (Point, Point)) -> bool {
+    let between = |value: i32, a: i32, b: i32| a.min(b) <= value && value <= a.max(b);
+    (point.0 == a.0 && a.0 == b.0 && between(point.1, a.1, b.1))
+        || (point.1 == a.1 && a.1 == b.1 && between(point.0, a.0, b.0))
+}
+
+/// Spawns the gates a Logisim gate is made of around its position.
+struct Helpers<'a, 'w, 's> {
+    commands: &'a mut Commands<'w, 's>,
+    position: Vec2,
+    count: usize,
+}
+
+impl Helpers<'_, '_, '_> {
+    fn spawn(&mut self, gate: Gate) -> Entity {
+        let position = self.position + HELPER_OFFSET * Vec2::new(1.0, self.count as f32);
+        self.count += 1;
+        let transform = Transform::from_translation(position.extend(0.0));
+        self.commands.spawn((gate, transform)).id()
+    }
+
+    fn combine(&mut self, operation: Operation, a: Entity, b: Entity) -> Gate {
+        match operation {
+            Operation::And => Gate::And(Some(a), Some(b)),
+            Operation::Or => Gate::Or(Some(a), Some(b)),
+            Operation::Xor => {
+                let (not_a, not_b) = (
+                    self.spawn(Gate::Not(Some(a))),
+                    self.spawn(Gate::Not(Some(b))),
+                );
+                let left = self.spawn(Gate::And(Some(a), Some(not_b)));
+                let right = self.spawn(Gate::And(Some(not_a), Some(b)));
+                Gate::Or(Some(left), Some(right))
+            }
+        }
+    }
+
+    /// Joins `inputs` with a balanced tree of two-input gates.
+    fn tree(&mut self, operation: Operation, inputs: &[Entity]) -> Option<Entity> {
+        match inputs {
+            [] => None,
+            [input] => Some(*input),
+            _ => {
+                let (left, right) = inputs.split_at(inputs.len() / 2);
+                let (left, right) = (self.tree(operation, left)?, self.tree(operation, right)?);
+                let gate = self.combine(operation, left, right);
+                Some(self.spawn(gate))
+            }
+        }
+    }
+}
+
+/// The value of the attribute `key` of the Logisim component `comp`.
+fn comp_attribute<'a>(comp: &'a Element, key: &str) -> Option<&'a str> {
+    comp.children("a")
+        .find(|a| a.attribute("name") == Some(key))
+        .and_then(|a| a.attribute("val"))
+}
+
+/// Offsets of the ports of a subcircuit from its anchor, one for each of
+/// its `pins` given with their location and facing, after Logisim's
+/// default appearance: a box with the pins on the side opposite to their
+/// facing, in order of location.
+fn port_offsets(pins: &[(Point, &str)]) -> Vec<Point> {
+    // The pins on the west, east, north and south sides.
+    let mut sides: [Vec<usize>; 4] = Default::default();
+    for (index, (_, facing)) in pins.iter().enumerate() {
+        let side = match *facing {
+            "west" => 1,
+            "south" => 2,
+            "north" => 3,
+            _ => 0,
+        };
+        sides[side].push(index);
+    }
+    for (side, members) in sides.iter_mut().enumerate() {
+        members.sort_by_key(|index| {
+            let (x, y) = pins[*index].0;
+            if side < 2 { (y, x) } else { (x, y) }
+        });
+    }
+
+    let [west, east, north, south] = sides.each_ref().map(|members| members.len() as i32);
+    let (vertical, horizontal) = (north.max(south), west.max(east));
+    let dimension = |count: i32, others: i32| match (count, others) {
+        (0..3, _) => 30,
+        (_, 0) => 10 * count,
+        _ => 10 * count + 10,
+    };
+    let offset = |count: i32, opposite: i32, others: i32| {
+        let most = count.max(opposite);
+        let start = match most {
+            0 | 1 if others == 0 => 15,
+            0..=2 => 10,
+            _ if others == 0 => 5,
+            _ => 10,
+        };
+        start + 10 * ((most - count) / 2)
+    };
+    let (width, height) = (
+        dimension(vertical, horizontal),
+        dimension(horizontal, vertical),
+    );
+    let starts = [
+        (0, offset(west, east, vertical)),
+        (width, offset(east, west, vertical)),
+        (offset(north, south, horizontal), 0),
+        (offset(south, north, horizontal), height),
+    ];
+    let anchor = match (east, north, west, south) {
+        (1.., ..) => starts[1],
+        (_, 1.., ..) => starts[2],
+        (_, _, 1.., _) => starts[0],
+        (.., 1..) => starts[3],
+        _ => (0, 0),
+    };
+
+    let mut offsets = vec![(0, 0); pins.len()];
+    for (side, members) in sides.iter().enumerate() {
+        let (x, y) = starts[side];
+        for (rank, index) in members.iter().enumerate() {
+            let step = 10 * rank as i32;
+            let (x, y) = if side < 2 {
+                (x, y + step)
+            } else {
+                (x + step, y)
+            };
+            offsets[*index] = (x - anchor.0, y - anchor.1);
+        }
+    }
+    offsets
+}
+
+/// Reads the Logisim component `comp` into a part, spawning its entity, or
+/// gives the name to report it under when it has no counterpart.
+fn read_part(
+    comp: &Element,
+    scope: usize,
+    default_inputs: i32,
+    origin: Vec2,
+    commands: &mut Commands,
+) -> Result<Part, String> {
+    let name = comp.attribute("name").unwrap_or_default();
+    let attribute = |key: &str| comp_attribute(comp, key);
+    let Some(location) = comp.attribute("loc").and_then(point) else {
+        return Err(format!("{name} without a location"));
+    };
+    let facing = attribute("facing").unwrap_or("east");
+    let label = attribute("label").filter(|label| !label.is_empty());
+    let width = match attribute("width") {
+        None => 1,
+        Some(width) => match width.parse() {
+            Ok(width @ 1..=MAX_WIDTH) => width,
+            _ => return Err(format!("{name} of width {width}")),
+        },
+    };
+    let offset = |offset: Point| {
+        let (dx, dy) = rotate(offset, facing);
+        (location.0 + dx, location.1 + dy)
+    };
+    let place = |point: Point| origin + Vec2::new(point.0 as f32, -point.1 as f32) * SCALE;
+
+    let logic = match name {
+        "AND Gate" => Some((Operation::And, false)),
+        "NAND Gate" => Some((Operation::And, true)),
+        "OR Gate" => Some((Operation::Or, false)),
+        "NOR Gate" => Some((Operation::Or, true)),
+        "XOR Gate" | "Odd Parity" => Some((Operation::Xor, false)),
+        "XNOR Gate" | "Even Parity" => Some((Operation::Xor, true)),
+        "NOT Gate" | "Buffer" => Some((Operation::And, name == "NOT Gate")),
+        _ => None,
+    };
+    let (inputs, output, build, center) = if let Some((operation, inverted)) = logic {
+        let single = matches!(name, "NOT Gate" | "Buffer");
+        let size = match (name, attribute("size")) {
+            ("Buffer", _) | ("NOT Gate", Some("narrow" | "20")) => 20,
+            ("NOT Gate", _) | (_, Some("narrow" | "30")) => 30,
+            (_, Some("wide" | "70")) => 70,
+            _ => 50,
+        };
+        let count = match (single, attribute("inputs")) {
+            (true, _) => 1,
+            (false, None) => default_inputs,
+            (false, Some(inputs)) => match inputs.parse() {
+                Ok(inputs @ 1..=MAX_GATE_INPUTS) => inputs,
+                _ => return Err(format!("{name} with {inputs} inputs")),
+            },
+        };
+        let body = match operation {
+            Operation::Xor => size + 10,
+            _ => size,
+        };
+        let negated = (0..count)
+            .map(|index| attribute(&format!("negate{index}")) == Some("true"))
+            .collect::<Vec<_>>();
+        let inputs = (0..count)
+            .map(|index| {
+                let (dx, dy) = match single {
+                    true => (-size, 0),
+                    false => gate_input(body, count, index),
+                };
+                offset((dx - 10 * negated[index as usize] as i32, dy))
+            })
+            .collect();
+        let build = Build::Logic {
+            operation,
+            inverted,
+            negated,
+        };
+        (inputs, Some(location), build, offset((-body / 2, 0)))
+    } else {
+        let sink = |point| (vec![point], None, Build::Output, location);
+        match name {
+            "Controlled Buffer" => {
+                let control = match attribute("control") {
+                    Some("left") => (-10, -10),
+                    _ => (-10, 10),
+                };
+                let inputs = vec![offset((-20, 0)), offset(control)];
+                (inputs, Some(location), Build::TriState, offset((-10, 0)))
+            }
+            "Pin" if attribute("output") == Some("true") => sink(location),
+            // Our buttons only give single bits.
+            "Pin" if width > 1 => return Err(format!("input Pin of {width} bits")),
+            "LED" | "Probe" => sink(location),
+            "Tunnel" => {
+                let name = label.unwrap_or_default().to_string();
+                (vec![location], None, Build::Tunnel(name), location)
+            }
+            "Pin" | "Button" | "Clock" | "Constant" | "Power" | "Ground" | "Pull Resistor" => {
+                (vec![], Some(location), Build::Source, location)
+            }
+            _ => return Err(name.to_string()),
+        }
+    };
+
+    let value = match (name, attribute("value")) {
+        ("Constant", Some(value)) => u64::from_str_radix(value.trim_start_matches("0x"), 16)
+            .map_err(|_| format!("Constant of value {value}"))?,
+        _ => 1,
+    };
+
+    let mut entity = commands.spawn(Transform::from_translation(place(center).extend(0.0)));
+    match name {
+        "Pin" if matches!(build, Build::Source) => {
+            entity.insert(LogicButton::default());
+        }
+        "Button" => {
+            entity.insert(LogicButton {
+                kind: ButtonKind::Momentary,
+                key: None,
+            });
+        }
+        "Clock" => {
+            entity.insert(Clock);
+        }
+        "Constant" => {
+            entity.insert(Constant::Word(Signal::new(width, value)));
+        }
+        "Power" => {
+            entity.insert(Constant::Power);
+        }
+        "Ground" => {
+            entity.insert(Constant::Ground);
+        }
+        "Pull Resistor" => {
+            entity.insert(match attribute("pull") {
+                Some("1") => Pull::Up,
+                _ => Pull::Down,
+            });
+        }
+        _ => {}
+    }
+    if let Some(label) = label
+        && !matches!(build, Build::Tunnel(_))
+    {
+        entity.insert(ItemLabel(label.to_string()));
+    }
+
+    Ok(Part {
+        scope,
+        entity: entity.id(),
+        position: place(center),
+        output,
+        inputs,
+        build,
+    })
+}
+
+/// The parts and wires of the circuits placed so far, subcircuits being
+/// flattened into the circuit using them.
+struct Flattening<'a, 'w, 's> {
+    project: &'a Element,
+    default_inputs: i32,
+    commands: &'a mut Commands<'w, 's>,
+    parts: Vec<Part>,
+    wires: Vec<(Spot, Spot)>,
+    /// Points joined without a wire: the ports of the subcircuits and the
+    /// pins inside them.
+    joints: Vec<(Spot, Spot)>,
+    /// The names of the components not imported, and how many of each.
+    skipped: Vec<(String, usize)>,
+    scopes: usize,
+}
+
+impl Flattening<'_, '_, '_> {
+    /// Places `circuit` with its top left corner at `origin`, returning the
+    /// scope of its points. The pins of a subcircuit are left out, its
+    /// ports standing for them.
+    fn place(&mut self, circuit: &Element, origin: Vec2, depth: usize) -> usize {
+        let scope = self.scopes;
+        self.scopes += 1;
+
+        let wires = circuit
+            .children("wire")
+            .filter_map(|wire| {
+                Some((
+                    point(wire.attribute("from")?)?,
+                    point(wire.attribute("to")?)?,
+                ))
+            })
+            .collect::<Vec<_>>();
+        let locations = circuit
+            .children("comp")
+            .filter_map(|comp| point(comp.attribute("loc")?))
+            .chain(wires.iter().flat_map(|(a, b)| [*a, *b]));
+        let corner = locations
+            .reduce(|a, b| (a.0.min(b.0), a.1.min(b.1)))
+            .unwrap_or((0, 0));
+        let origin = origin - Vec2::new(corner.0 as f32, -corner.1 as f32) * SCALE;
+        let wires = wires.into_iter().map(|(a, b)| ((scope, a), (scope, b)));
+        self.wires.extend(wires);
+
+        for comp in circuit.children("comp") {
+            let name = comp.attribute("name").unwrap_or_default();
+            let result = match comp.attribute("lib") {
+                None => self.place_subcircuit(comp, scope, origin, depth),
+                Some(_) if depth > 0 && name == "Pin" => Ok(()),
+                Some(_) => {
+                    let part = read_part(comp, scope, self.default_inputs, origin, self.commands);
+                    part.map(|mut part| {
+                        // Tunnels only join within their circuit.
+                        if let Build::Tunnel(tunnel) = &mut part.build
+                            && depth > 0
+                        {
+                            let circuit = circuit.attribute("name").unwrap_or_default();
+                            *tunnel = format!("{circuit}{scope}.{tunnel}");
+                        }
+                        self.parts.push(part);
+                    })
+                }
+            };
+            if let Err(name) = result {
+                match self.skipped.iter_mut().find(|(other, _)| *other == name) {
+                    Some((_, count)) => *count += 1,
+                    None => self.skipped.push((name, 1)),
+                }
+            }
+        }
+        scope
+    }
+
+    /// Places the circuit an instance `comp` in the circuit `scope` refers
+    /// to, its pins joining the ports of the instance.
+    fn place_subcircuit(
+        &mut self,
+        comp: &Element,
+        scope: usize,
+        origin: Vec2,
+        depth: usize,
+    ) -> Result<(), String> {
+        let name = comp.attribute("name").unwrap_or_default();
+        let Some(circuit) = self
+            .project
+            .children("circuit")
+            .find(|circuit| circuit.attribute("name") == Some(name))
+        else {
+            return Err(format!("subcircuit {name}"));
+        };
+        if depth >= MAX_DEPTH {
+            return Err(format!("subcircuit {name} including itself"));
+        }
+        if circuit.children("appear").next().is_some() {
+            return Err(format!("subcircuit {name} with a custom appearance"));
+        }
+        let Some(location) = comp.attribute("loc").and_then(point) else {
+            return Err(format!("{name} without a location"));
+        };
+
+        let pins = circuit
+            .children("comp")
+            .filter(|pin| pin.attribute("lib").is_some() && pin.attribute("name") == Some("Pin"))
+            .filter_map(|pin| {
+                let facing = comp_attribute(pin, "facing").unwrap_or("east");
+                Some((point(pin.attribute("loc")?)?, facing))
+            })
+            .collect::<Vec<_>>();
+        let position = origin + Vec2::new(location.0 as f32, -location.1 as f32) * SCALE;
+        let inner = self.place(circuit, position, depth + 1);
+
+        let facing = comp_attribute(comp, "facing").unwrap_or("east");
+        for ((pin, _), offset) in pins.iter().zip(port_offsets(&pins)) {
+            let (dx, dy) = rotate(offset, facing);
+            let port = (location.0 + dx, location.1 + dy);
+            self.joints.push(((scope, port), (inner, *pin)));
+        }
+        Ok(())
+    }
+}
+
+/// Places the circuit `circuit` of a Logisim project, or its main circuit,
+/// with its top left corner at `origin`, flattening the subcircuits it
+/// uses. Gives the components that could not be imported.
+pub fn import(
+    text: &str,
+    circuit: Option<&str>,
+    commands: &mut Commands,
+    origin: Vec2,
+) -> Result<Vec<String>, String> {
+    let project = xml::parse(text)?;
+    if project.name != "project" {
+        return Err("not a Logisim project".to_string());
+    }
+    let main = project
+        .children("main")
+        .find_map(|main| main.attribute("name"));
+    let Some(circuit) = circuit.or(main).or_else(|| {
+        project
+            .children("circuit")
+            .find_map(|circuit| circuit.attribute("name"))
+    }) else {
+        return Err("the project has no circuit".to_string());
+    };
+    let Some(element) = project
+        .children("circuit")
+        .find(|element| element.attribute("name") == Some(circuit))
+    else {
+        return Err(format!("the project has no circuit named {circuit}"));
+    };
+    // Logisim 2 gates have five inputs unless told otherwise, later ones two.
+    let default_inputs = match project.attribute("source") {
+        Some(source) if source.starts_with("2.") => 5,
+        _ => 2,
+    };
+
+    let mut flattening = Flattening {
+        project: &project,
+        default_inputs,
+        commands,
+        parts: Vec::new(),
+        wires: Vec::new(),
+        joints: Vec::new(),
+        skipped: Vec::new(),
+        scopes: 0,
+    };
+    flattening.place(element, origin, 0);
+    let Flattening {
+        parts,
+        wires,
+        joints,
+        skipped,
+        ..
+    } = flattening;
+
+    let mut connections = Connections::default();
+    for (a, b) in wires.iter().chain(&joints) {
+        connections.join(*a, *b);
+    }
+    let points = parts
+        .iter()
+        .flat_map(|part| {
+            let points = part.inputs.iter().chain(&part.output);
+            points.map(|point| (part.scope, *point))
+        })
+        .chain(wires.iter().chain(&joints).flat_map(|(a, b)| [*a, *b]))
+        .collect::<Vec<_>>();
+    for point in points {
+        let crossed = wires
+            .iter()
+            .filter(|(a, b)| a.0 == point.0 && on_wire(point.1, (a.1, b.1)));
+        for wire in crossed {
+            connections.join(point, wire.0);
+        }
+    }
+
+    let mut drivers: HashMap<Spot, Vec<(Entity, Vec2)>> = HashMap::new();
+    let mut tunnels: HashMap<Spot, Entity> = HashMap::new();
+    for part in &parts {
+        if let Some(output) = part.output {
+            let net = connections.find((part.scope, output));
+            drivers
+                .entry(net)
+                .or_default()
+                .push((part.entity, part.position));
+        }
+        if let (Build::Tunnel(_), Some(point)) = (&part.build, part.inputs.first()) {
+            tunnels.insert(connections.find((part.scope, *point)), part.entity);
+        }
+    }
+
+    // Our inputs take a single item, so several drivers meet in tunnels.
+    let mut driving = HashMap::new();
+    for (index, (net, net_drivers)) in drivers.iter().enumerate() {
+        let driver = match net_drivers.as_slice() {
+            [(driver, _)] => *driver,
+            _ => {
+                let shared = net_drivers.iter().map(|(driver, position)| {
+                    let tunnel = Tunnel {
+                        name: format!("net{index}"),
+                        input: Some(*driver),
+                    };
+                    let transform =
+                        Transform::from_translation((*position + HELPER_OFFSET).extend(0.0));
+                    commands.spawn((tunnel, transform)).id()
+                });
+                let Some(first) = shared.collect::<Vec<_>>().first().copied() else {
+                    continue;
+                };
+                first
+            }
+        };
+        driving.insert(*net, driver);
+    }
+
+    for part in parts {
+        let scope = part.scope;
+        let mut nets = part
+            .inputs
+            .iter()
+            .map(|point| connections.find((scope, *point)));
+        let mut input = |tunnel: bool| {
+            let net = nets.next()?;
+            driving
+                .get(&net)
+                .or(tunnel.then(|| tunnels.get(&net)).flatten())
+                .copied()
+        };
+        match part.build {
+            Build::Source => {}
+            Build::Output => {
+                commands.entity(part.entity).insert(Junction(input(true)));
+            }
+            Build::Tunnel(name) => {
+                let input = input(false);
+                commands.entity(part.entity).insert(Tunnel { name, input });
+            }
+            Build::TriState => {
+                let (data, enable) = (input(true), input(true));
+                commands
+                    .entity(part.entity)
+                    .insert(Gate::TriState(data, enable));
+            }
+            Build::Logic {
+                operation,
+                inverted,
+                negated,
+            } => {
+                let inputs = negated.iter().map(|negated| (input(true), *negated));
+                let inputs = inputs.collect::<Vec<_>>();
+                let mut helpers = Helpers {
+                    commands,
+                    position: part.position,
+                    count: 0,
+                };
+                // Logisim leaves unconnected gate inputs out.
+                let inputs = inputs
+                    .into_iter()
+                    .filter_map(|(input, negated)| match negated {
+                        true => Some(helpers.spawn(Gate::Not(Some(input?)))),
+                        false => input,
+                    })
+                    .collect::<Vec<_>>();
+
+                let gate = match (inputs.as_slice(), inverted) {
+                    ([] | [_], false) => {
+                        let junction = Junction(inputs.first().copied());
+                        helpers.commands.entity(part.entity).insert(junction);
+                        continue;
+                    }
+                    ([] | [_], true) => Gate::Not(inputs.first().copied()),
+                    (inputs, false) => {
+                        let (left, right) = inputs.split_at(inputs.len() / 2);
+                        match (
+                            helpers.tree(operation, left),
+                            helpers.tree(operation, right),
+                        ) {
+                            (Some(left), Some(right)) => helpers.combine(operation, left, right),
+                            (left, right) => Gate::And(left, right),
+                        }
+                    }
+                    (inputs, true) => Gate::Not(helpers.tree(operation, inputs)),
+                };
+                helpers.commands.entity(part.entity).insert(gate);
+            }
+        }
+    }
+
+    let skipped = skipped
+        .into_iter()
+        .map(|(name, count)| format!("{count} x {name} not imported"));
+    Ok(skipped.collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    fn import_into(world: &mut World, comps: &'static str) -> Vec<String> {
+        let text =
+            format!("<project source=\"3.8.0\"><circuit name=\"main\">{comps}</circuit></project>");
+        world
+            .run_system_once(move |mut commands: Commands| {
+                import(&text, None, &mut commands, Vec2::ZERO)
+            })
+            .unwrap()
+            .unwrap()
+    }
+
+    fn import_circuit(comps: &'static str) -> Vec<String> {
+        import_into(&mut World::new(), comps)
+    }
+
+    #[test]
+    fn points_and_facings() {
+        assert_eq!(point(" (10, -20)"), Some((10, -20)));
+        assert_eq!(point("10,20"), None);
+        assert_eq!(rotate((-30, 10), "east"), (-30, 10));
+        assert_eq!(rotate((-30, 10), "west"), (30, -10));
+        assert_eq!(rotate((-30, 10), "north"), (10, 30));
+        assert_eq!(rotate((-30, 10), "south"), (-10, -30));
+    }
+
+    #[test]
+    fn gate_inputs_follow_logisim() {
+        let inputs = |size, count| {
+            (0..count)
+                .map(|index| gate_input(size, count, index).1)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(inputs(50, 2), [-20, 20]);
+        assert_eq!(inputs(50, 3), [-20, 0, 20]);
+        assert_eq!(inputs(50, 5), [-20, -10, 0, 10, 20]);
+        assert_eq!(inputs(30, 2), [-10, 10]);
+        assert_eq!(gate_input(70, 2, 0).0, -70);
+    }
+
+    #[test]
+    fn subcircuit_ports_surround_the_box() {
+        // Two inputs on the west side, one output on the east side, which
+        // is the anchor.
+        let pins = [((40, 60), "east"), ((40, 20), "east"), ((200, 40), "west")];
+        assert_eq!(port_offsets(&pins), [(-30, 10), (-30, 0), (0, 0)]);
+    }
+
+    #[test]
+    fn wires_connect_pins_to_gates() {
+        let mut world = World::new();
+        let skipped = import_into(
+            &mut world,
+            "<wire from=\"(40,20)\" to=\"(50,20)\"/>\
+             <comp lib=\"0\" loc=\"(40,20)\" name=\"Pin\"><a name=\"label\" val=\"a\"/></comp>\
+             <comp lib=\"0\" loc=\"(50,60)\" name=\"Pin\"><a name=\"label\" val=\"b\"/></comp>\
+             <comp lib=\"1\" loc=\"(100,40)\" name=\"AND Gate\"/>",
+        );
+        assert!(skipped.is_empty());
+
+        let mut labels = world.query::<(Entity, &ItemLabel)>();
+        let mut pin = |name: &str| {
+            let mut labelled = labels.iter(&world).filter(|(_, label)| label.0 == name);
+            labelled.next().map(|(entity, _)| entity)
+        };
+        let (a, b) = (pin("a"), pin("b"));
+        let mut gates = world.query::<&Gate>();
+        let gates = gates.iter(&world).collect::<Vec<_>>();
+        let [Gate::And(first, second)] = gates.as_slice() else {
+            panic!("expected a single AND gate");
+        };
+        assert_eq!((*first, *second), (a, b));
+    }
+
+    #[test]
+    fn attributes_out_of_range_are_not_imported() {
+        let skipped = import_circuit(
+            "<comp lib=\"0\" loc=\"(0,0)\" name=\"Pin\"><a name=\"width\" val=\"8\"/></comp>\
+             <comp lib=\"0\" loc=\"(0,20)\" name=\"Pin\"><a name=\"width\" val=\"99\"/></comp>\
+             <comp lib=\"1\" loc=\"(90,0)\" name=\"AND Gate\">\
+               <a name=\"inputs\" val=\"100000\"/></comp>\
+             <comp lib=\"0\" loc=\"(0,40)\" name=\"Constant\"><a name=\"value\" val=\"0xzz\"/></comp>",
+        );
+        assert_eq!(
+            skipped,
+            [
+                "1 x input Pin of 8 bits not imported",
+                "1 x Pin of width 99 not imported",
+                "1 x AND Gate with 100000 inputs not imported",
+                "1 x Constant of value 0xzz not imported",
+            ]
+        );
+    }
+
+    #[test]
+    fn supported_components_are_imported() {
+        let skipped = import_circuit(
+            "<comp lib=\"0\" loc=\"(0,0)\" name=\"Pin\"/>\
+             <comp lib=\"0\" loc=\"(0,40)\" name=\"Constant\">\
+               <a name=\"width\" val=\"4\"/><a name=\"value\" val=\"0xa\"/></comp>\
+             <comp lib=\"1\" loc=\"(90,0)\" name=\"AND Gate\"><a name=\"inputs\" val=\"3\"/></comp>",
+        );
+        assert!(skipped.is_empty());
+    }
+}
@@ -0,0 +1,206 @@
+use std::{fs, path::Path};
+
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+
+use crate::{
+    analysis::{
+        expression::Expr,
+        network::{Network, Node},
+    },
+    console::{Console, ConsoleAppExt, ConsoleCommand},
+    cursor::CursorPosition,
+    netlist::verilog::VerilogPlugin,
+};
+
+pub mod blif;
+pub mod logisim;
+pub mod verilog;
+pub mod xml;
+
+/// Exchange of circuits with other tools as gate-level netlists.
+pub struct NetlistPlugin;
+impl Plugin for NetlistPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(VerilogPlugin)
+            .add_console_command(
+                "import",
+                "import <path> [circuit], place at the cursor a BLIF (.blif) or structural \
+                 Verilog (.v) netlist, or a circuit of a Logisim project (.circ)",
+            )
+            .add_systems(Update, import_commands);
+    }
+}
+
+/// A gate-level design read from a file: its ports, and the expression
+/// computing each other signal from the signals it names.
+#[derive(Default)]
+pub struct Netlist {
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+    pub signals: HashMap<String, Expr>,
+}
+
+impl Netlist {
+    pub fn define(&mut self, name: &str, expr: Expr) -> Result<(), String> {
+        match self.signals.insert(name.to_string(), expr) {
+            Some(_) => Err(format!("`{name}` is driven twice")),
+            None => Ok(()),
+        }
+    }
+
+    /// The gates computing the outputs from the inputs.
+    pub fn network(&self) -> Result<Network, String> {
+        let mut builder = Builder {
+            netlist: self,
+            network: Network::default(),
+            nodes: HashMap::new(),
+            visiting: HashSet::new(),
+        };
+        for input in &self.inputs {
+            let node = builder.network.input(input);
+            builder.nodes.insert(input.clone(), node);
+        }
+        for output in &self.outputs {
+            let node = builder.signal(output)?;
+            builder.network.outputs.push((output.clone(), node));
+        }
+        Ok(builder.network)
+    }
+}
+
+#[cfg(test)]
+impl Netlist {
+    /// The value of the signal `name` for the given values of the inputs.
+    pub fn evaluate(&self, name: &str, inputs: &[(&str, bool)]) -> bool {
+        match inputs.iter().find(|(input, _)| *input == name) {
+            Some((_, level)) => *level,
+            None => self.signals[name].evaluate(&|name| self.evaluate(name, inputs)),
+        }
+    }
+}
+
+/// Lowers the expressions of a netlist onto the nodes of a network.
+struct Builder<'a> {
+    netlist: &'a Netlist,
+    network: Network,
+    /// The node computing each signal already lowered.
+    nodes: HashMap<String, usize>,
+    /// The signals being lowered, to catch combinational loops.
+    visiting: HashSet<String>,
+}
+
+impl Builder<'_> {
+    fn signal(&mut self, name: &str) -> Result<usize, String> {
+        if let Some(node) = self.nodes.get(name) {
+            return Ok(*node);
+        }
+        let Some(expr) = self.netlist.signals.get(name) else {
+            return Err(format!("`{name}` is not driven"));
+        };
+        if !self.visiting.insert(name.to_string()) {
+            return Err(format!("`{name}` depends on itself"));
+        }
+
+        let node = self.expr(expr)?;
+        self.visiting.remove(name);
+        self.nodes.insert(name.to_string(), node);
+        Ok(node)
+    }
+
+    fn expr(&mut self, expr: &Expr) -> Result<usize, String> {
+        let node = match expr {
+            Expr::Constant(constant) => Node::Constant(*constant),
+            Expr::Variable(name) => return self.signal(name),
+            Expr::Not(a) => Node::Not(self.expr(a)?),
+            Expr::And(a, b) => Node::And(self.expr(a)?, self.expr(b)?),
+            Expr::Or(a, b) => Node::Or(self.expr(a)?, self.expr(b)?),
+            Expr::Xor(a, b) => {
+                let (a, b) = (self.expr(a)?, self.expr(b)?);
+                let (not_a, not_b) = (
+                    self.network.push(Node::Not(a)),
+                    self.network.push(Node::Not(b)),
+                );
+                let left = self.network.push(Node::And(a, not_b));
+                let right = self.network.push(Node::And(not_a, b));
+                Node::Or(left, right)
+            }
+        };
+        // Each cell of the design keeps its own gate, even when another one
+        // computes the same.
+        Ok(self.network.push(node))
+    }
+}
+
+/// Places the design in the file at `path` with its top left corner at
+/// `origin`, giving what could not be imported.
+fn import(
+    path: &str,
+    circuit: Option<&str>,
+    commands: &mut Commands,
+    origin: Vec2,
+) -> Result<Vec<String>, String> {
+    let text = fs::read_to_string(path).map_err(|error| format!("cannot read {path}: {error}"))?;
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default();
+    let netlist = match extension.to_ascii_lowercase().as_str() {
+        "blif" => blif::parse(&text)?,
+        "v" => verilog::parse(&text)?,
+        "circ" => return logisim::import(&text, circuit, commands, origin),
+        _ => {
+            return Err(format!(
+                "cannot tell the format of {path}, use .blif, .v or .circ"
+            ));
+        }
+    };
+    netlist.network()?.spawn(commands, origin);
+    Ok(Vec::new())
+}
+
+fn import_commands(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    mut commands: Commands,
+    cursor: Res<CursorPosition>,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        if command.name != "import" {
+            continue;
+        }
+
+        let args = command.args();
+        let (path, circuit) = match args.as_slice() {
+            [path] => (path, None),
+            [path, circuit] => (path, Some(*circuit)),
+            _ => {
+                console.print("usage: import <path> [circuit]");
+                continue;
+            }
+        };
+        match import(path, circuit, &mut commands, cursor.in_world) {
+            Ok(skipped) => {
+                for message in skipped {
+                    console.print(message);
+                }
+            }
+            Err(error) => console.print(error),
+        }
+    }
+}
+
+/// Turns a label into an identifier other tools accept, replacing anything
+/// but letters, digits and underscores.
+pub fn identifier(name: &str) -> String {
+    let mut identifier = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    if !identifier.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        identifier.insert(0, '_');
+    }
+    identifier
+}
@@ -0,0 +1,720 @@
+use std::{fs, path::Path};
+
+use bevy::{
+    ecs::system::SystemParam,
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+use ergo_core::simulation::Wiring;
+
+use crate::{
+    analysis::{expression::Expr, port_name},
+    console::{Console, ConsoleAppExt, ConsoleCommand},
+    logic::{
+        Gate, Item, ItemLabel, Junction, LogicButton, Nets, OutputPin, Tunnel, Value,
+        bus::{Merger, Splitter},
+        clock::Clock,
+        source::{Constant, Pull},
+    },
+    netlist::{Netlist, identifier},
+    signal::{Level, Signal},
+};
+
+/// The reserved words of Verilog (IEEE 1364-2005), which names must not
+/// collide with.
+const KEYWORDS: [&str; 124] = [
+    "always",
+    "and",
+    "assign",
+    "automatic",
+    "begin",
+    "buf",
+    "bufif0",
+    "bufif1",
+    "case",
+    "casex",
+    "casez",
+    "cell",
+    "cmos",
+    "config",
+    "deassign",
+    "default",
+    "defparam",
+    "design",
+    "disable",
+    "edge",
+    "else",
+    "end",
+    "endcase",
+    "endconfig",
+    "endfunction",
+    "endgenerate",
+    "endmodule",
+    "endprimitive",
+    "endspecify",
+    "endtable",
+    "endtask",
+    "event",
+    "for",
+    "force",
+    "forever",
+    "fork",
+    "function",
+    "generate",
+    "genvar",
+    "highz0",
+    "highz1",
+    "if",
+    "ifnone",
+    "incdir",
+    "include",
+    "initial",
+    "inout",
+    "input",
+    "instance",
+    "integer",
+    "join",
+    "large",
+    "liblist",
+    "library",
+    "localparam",
+    "macromodule",
+    "medium",
+    "module",
+    "nand",
+    "negedge",
+    "nmos",
+    "nor",
+    "noshowcancelled",
+    "not",
+    "notif0",
+    "notif1",
+    "or",
+    "output",
+    "parameter",
+    "pmos",
+    "posedge",
+    "primitive",
+    "pull0",
+    "pull1",
+    "pulldown",
+    "pullup",
+    "pulsestyle_ondetect",
+    "pulsestyle_onevent",
+    "rcmos",
+    "real",
+    "realtime",
+    "reg",
+    "release",
+    "repeat",
+    "rnmos",
+    "rpmos",
+    "rtran",
+    "rtranif0",
+    "rtranif1",
+    "scalared",
+    "showcancelled",
+    "signed",
+    "small",
+    "specify",
+    "specparam",
+    "strong0",
+    "strong1",
+    "supply0",
+    "supply1",
+    "table",
+    "task",
+    "time",
+    "tran",
+    "tranif0",
+    "tranif1",
+    "tri",
+    "tri0",
+    "tri1",
+    "triand",
+    "trior",
+    "trireg",
+    "unsigned",
+    "use",
+    "uwire",
+    "vectored",
+    "wait",
+    "wand",
+    "weak0",
+    "weak1",
+    "while",
+    "wire",
+    "wor",
+    "xnor",
+    "xor",
+];
+
+pub struct VerilogPlugin;
+impl Plugin for VerilogPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_console_command(
+            "verilog",
+            "verilog <path> [module], export the circuit as structural Verilog, buttons and \
+             clocks as inputs and labelled items as outputs",
+        )
+        .add_systems(Update, verilog_commands);
+    }
+}
+
+/// The names given while writing a module.
+#[derive(Default)]
+struct Names {
+    used: HashSet<String>,
+    /// The wire carrying the value of each item driving a net.
+    wires: HashMap<Entity, String>,
+    /// The nets driven by several items or pulled, with the wire they get.
+    nets: Vec<(Vec<Entity>, Level, String)>,
+}
+
+impl Names {
+    fn unique(&mut self, name: String) -> String {
+        let mut unique = name.clone();
+        let mut suffix = 1;
+        while KEYWORDS.contains(&unique.as_str()) || !self.used.insert(unique.clone()) {
+            unique = format!("{name}_{suffix}");
+            suffix += 1;
+        }
+        unique
+    }
+}
+
+fn range(width: u8) -> String {
+    match width {
+        0 | 1 => String::new(),
+        width => format!("[{}:0] ", width - 1),
+    }
+}
+
+/// A constant as a sized literal, in binary for single bits so that
+/// [`parse`] reads it back.
+fn literal(signal: Signal) -> String {
+    match signal.value() {
+        Some(value) if signal.width() > 1 => format!("{}'h{value:x}", signal.width()),
+        // Single bits and unknown values are displayed bit by bit, as 0, 1,
+        // x or z.
+        _ => format!("{}'b{signal}", signal.width()),
+    }
+}
+
+/// Reads the circuit as a netlist of the components Verilog has a structural
+/// counterpart for.
+#[derive(SystemParam)]
+#[allow(clippy::type_complexity)]
+pub struct Design<'w, 's> {
+    items: Query<'w, 's, (Entity, Option<&'static ItemLabel>), With<Item>>,
+    values: Query<'w, 's, &'static Value>,
+    nets: Nets<'w, 's>,
+    gates: Query<'w, 's, &'static Gate>,
+    splitters: Query<'w, 's, (&'static Splitter, &'static Children)>,
+    mergers: Query<'w, 's, &'static Merger>,
+    constants: Query<'w, 's, &'static Constant>,
+    sources: Query<'w, 's, (), Or<(With<LogicButton>, With<Clock>)>>,
+    buttons: Query<'w, 's, (), With<LogicButton>>,
+    wiring: Query<'w, 's, (), Or<(With<Junction>, With<Tunnel>, With<Pull>)>>,
+    tunnels: Query<'w, 's, &'static Tunnel>,
+    pins: Query<'w, 's, (&'static OutputPin, &'static ChildOf)>,
+}
+
+impl Design<'_, '_> {
+    fn width(&self, entity: Entity) -> u8 {
+        self.values
+            .get(entity)
+            .map_or(1, |value| value.state.width())
+    }
+
+    /// Whether the item computes a value the module has a wire for.
+    fn is_driver(&self, entity: Entity) -> bool {
+        self.gates.contains(entity)
+            || self.mergers.contains(entity)
+            || self.constants.contains(entity)
+            || self.sources.contains(entity)
+            || self
+                .pins
+                .get(entity)
+                .is_ok_and(|(_, parent)| self.splitters.contains(parent.parent()))
+    }
+
+    /// The expression reading `input`: the wire of its only driver, or the
+    /// wire standing for its net.
+    fn operand(&self, input: Option<Entity>, names: &mut Names) -> String {
+        let Some(input) = input else {
+            return "1'bz".to_string();
+        };
+
+        let (mut drivers, pull) = self.nets.pulled_drivers(input);
+        if let ([driver], Level::HighZ) = (drivers.as_slice(), pull) {
+            return names.wires.get(driver).cloned().unwrap_or_default();
+        }
+
+        drivers.sort();
+        if let Some((_, _, name)) = names
+            .nets
+            .iter()
+            .find(|(other, level, _)| *other == drivers && *level == pull)
+        {
+            return name.clone();
+        }
+        let name = match self.tunnels.get(input) {
+            Ok(tunnel) => names.unique(identifier(&tunnel.name)),
+            Err(_) => names.unique(format!("net{}", names.nets.len())),
+        };
+        names.nets.push((drivers, pull, name.clone()));
+        name
+    }
+
+    /// The right-hand side of the assignment of a driver.
+    fn assignment(&self, entity: Entity, names: &mut Names) -> Option<String> {
+        if let Ok(gate) = self.gates.get(entity) {
+            let mut operand = |input| self.operand(input, names);
+            return Some(match gate {
+                Gate::And(a, b) => format!("{} & {}", operand(*a), operand(*b)),
+                Gate::Or(a, b) => format!("{} | {}", operand(*a), operand(*b)),
+                Gate::Not(a) => format!("~{}", operand(*a)),
+                Gate::TriState(data, enable) => {
+                    let width = self.width(entity);
+                    let (data, enable) = (operand(*data), operand(*enable));
+                    format!("{enable} ? {data} : {width}'bz")
+                }
+            });
+        }
+        if let Ok(merger) = self.mergers.get(entity) {
+            let inputs = merger
+                .inputs
+                .iter()
+                .rev()
+                .map(|input| self.operand(*input, names))
+                .collect::<Vec<_>>();
+            return Some(format!("{{{}}}", inputs.join(", ")));
+        }
+        if let Ok(constant) = self.constants.get(entity) {
+            return Some(literal(constant.signal()));
+        }
+        if let Ok((pin, parent)) = self.pins.get(entity)
+            && let Ok((splitter, _)) = self.splitters.get(parent.parent())
+        {
+            let bus = self.operand(splitter.input, names);
+            let bus_width = splitter
+                .input
+                .and_then(|input| self.nets.drivers(input).first().copied())
+                .map_or(1, |driver| self.width(driver));
+            return Some(match (bus.as_str(), bus_width) {
+                ("1'bz", _) => bus,
+                (_, 1) if pin.0 == 0 => bus,
+                (_, 1) => "1'bz".to_string(),
+                _ => format!("{bus}[{}]", pin.0),
+            });
+        }
+        None
+    }
+
+    /// The module computing the same as the circuit.
+    pub fn to_verilog(&self, module: &str) -> Result<String, String> {
+        let mut items = self.items.iter().collect::<Vec<_>>();
+        items.sort_by_key(|(entity, _)| entity.index());
+
+        let unsupported = items
+            .iter()
+            .filter(|(entity, _)| {
+                !self.is_driver(*entity)
+                    && !self.wiring.contains(*entity)
+                    && !self.splitters.contains(*entity)
+                    && !self.pins.contains(*entity)
+            })
+            .map(|(entity, label)| match label {
+                Some(label) => label.0.clone(),
+                None => format!("item {}", entity.index()),
+            })
+            .collect::<Vec<_>>();
+        if !unsupported.is_empty() {
+            return Err(format!(
+                "no structural Verilog for {}, only gates, buses, constants, buttons and \
+                 clocks are exported",
+                unsupported.join(", ")
+            ));
+        }
+
+        let mut names = Names::default();
+        let drivers = items
+            .iter()
+            .filter(|(entity, _)| self.is_driver(*entity))
+            .copied()
+            .collect::<Vec<_>>();
+        for (entity, label) in &drivers {
+            let name = match label {
+                Some(label) => identifier(&label.0),
+                None if self.buttons.contains(*entity) => port_name(*entity, true, None, None),
+                None => format!("n{}", entity.index()),
+            };
+            let name = names.unique(name);
+            names.wires.insert(*entity, name);
+        }
+
+        let mut ports = Vec::new();
+        let mut declarations = Vec::new();
+        let mut assignments = Vec::new();
+        for (entity, label) in &drivers {
+            let name = names.wires.get(entity).cloned().unwrap_or_default();
+            let range = range(self.width(*entity));
+            if self.sources.contains(*entity) {
+                ports.push(format!("input wire {range}{name}"));
+                continue;
+            }
+            match label {
+                Some(_) => ports.push(format!("output wire {range}{name}")),
+                None => declarations.push(format!("wire {range}{name};")),
+            }
+            if let Some(assignment) = self.assignment(*entity, &mut names) {
+                assignments.push(format!("assign {name} = {assignment};"));
+            }
+        }
+
+        // Labelled wiring carries an output of its own.
+        for (entity, label) in &items {
+            let Some(label) = label else {
+                continue;
+            };
+            if !self.wiring.contains(*entity) || self.nets.drivers(*entity).contains(entity) {
+                continue;
+            }
+            let name = names.unique(identifier(&label.0));
+            let drivers = self.nets.drivers(*entity);
+            let width = drivers.first().map_or(1, |driver| self.width(*driver));
+            ports.push(format!("output wire {}{name}", range(width)));
+            let operand = self.operand(Some(*entity), &mut names);
+            assignments.push(format!("assign {name} = {operand};"));
+        }
+
+        for (drivers, pull, name) in &names.nets {
+            let kind = match pull {
+                Level::High => "tri1",
+                Level::Low => "tri0",
+                _ => "wire",
+            };
+            let width = drivers.first().map_or(1, |driver| self.width(*driver));
+            declarations.push(format!("{kind} {}{name};", range(width)));
+            for driver in drivers {
+                let wire = names.wires.get(driver).cloned().unwrap_or_default();
+                assignments.push(format!("assign {name} = {wire};"));
+            }
+        }
+
+        let mut verilog = format!("module {} (\n", identifier(module));
+        let ports = ports
+            .iter()
+            .map(|port| format!("    {port}"))
+            .collect::<Vec<_>>();
+        verilog.push_str(&ports.join(",\n"));
+        verilog.push_str("\n);\n");
+        for line in declarations.iter().chain(&assignments) {
+            verilog.push_str(&format!("    {line}\n"));
+        }
+        verilog.push_str("endmodule\n");
+        Ok(verilog)
+    }
+}
+
+/// Drops the `//` and `/* */` comments.
+fn strip_comments(text: &str) -> String {
+    let mut stripped = String::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let line = rest.find("//");
+        let block = rest.find("/*");
+        let (start, end) = match (line, block) {
+            (Some(line), block) if block.is_none_or(|block| line < block) => {
+                let end = rest[line..].find('\n').map_or(rest.len(), |end| line + end);
+                (line, end)
+            }
+            (_, Some(block)) => {
+                let end = rest[block..]
+                    .find("*/")
+                    .map_or(rest.len(), |end| block + end + 2);
+                (block, end)
+            }
+            _ => (rest.len(), rest.len()),
+        };
+        stripped.push_str(&rest[..start]);
+        stripped.push(' ');
+        rest = &rest[end..];
+    }
+    stripped
+}
+
+/// Reads port and wire declarations such as `input a, b`, `output wire y`
+/// or `wire y = a & b`, defining the wires assigned in their declaration.
+fn declare(netlist: &mut Netlist, declaration: &str) -> Result<(), String> {
+    let mut direction = None;
+    for name in declaration.split(',') {
+        let (name, expression) = match name.split_once('=') {
+            Some((name, expression)) => (name, Some(expression)),
+            None => (name, None),
+        };
+        let mut words = name.split_whitespace().peekable();
+        while let Some(word) = words.next_if(|word| ["input", "output", "wire"].contains(word)) {
+            if word != "wire" {
+                direction = Some(word);
+            }
+        }
+        let name = words.collect::<Vec<_>>().join(" ");
+        if name.contains('[') {
+            return Err(format!(
+                "`{name}` is a vector, only single bits are supported"
+            ));
+        }
+        if let Some(expression) = expression {
+            assign(netlist, &name, expression)?;
+        }
+        match direction {
+            Some("input") => netlist.inputs.push(name),
+            Some("output") => netlist.outputs.push(name),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Defines `name` as the value of a continuous assignment.
+fn assign(netlist: &mut Netlist, name: &str, expression: &str) -> Result<(), String> {
+    if let Some(operator) = expression
+        .chars()
+        .find(|c| !(c.is_alphanumeric() || c.is_whitespace() || "_~&|^()".contains(*c)))
+    {
+        return Err(format!(
+            "`{operator}` is not supported in `{name} = {}`",
+            expression.trim()
+        ));
+    }
+    netlist.define(name.trim(), Expr::parse(expression)?)
+}
+
+/// Builds the expression of a binary operator from its operands.
+type Combine = fn(Box<Expr>, Box<Expr>) -> Expr;
+
+/// The expression of a gate primitive over its inputs.
+fn primitive(gate: &str, inputs: &[&str]) -> Option<Expr> {
+    let variables = inputs.iter().map(|input| Expr::Variable(input.to_string()));
+    let (combine, inverted): (Combine, bool) = match gate {
+        "and" => (Expr::And, false),
+        "nand" => (Expr::And, true),
+        "or" => (Expr::Or, false),
+        "nor" => (Expr::Or, true),
+        "xor" => (Expr::Xor, false),
+        "xnor" => (Expr::Xor, true),
+        "buf" => (Expr::And, false),
+        "not" => (Expr::And, true),
+        _ => return None,
+    };
+    let expr = variables.reduce(|a, b| combine(Box::new(a), Box::new(b)))?;
+    Some(match inverted {
+        true => Expr::Not(Box::new(expr)),
+        false => expr,
+    })
+}
+
+/// Reads a module made of scalar ports and wires, continuous assignments over
+/// `~ & ^ |` and the gate primitives `and or xor nand nor xnor buf not`.
+pub fn parse(text: &str) -> Result<Netlist, String> {
+    let text = strip_comments(text)
+        .replace("endmodule", ";")
+        .replace("1'b0", "0")
+        .replace("1'b1", "1");
+    let mut netlist = Netlist::default();
+
+    for statement in text.split(';').map(str::trim) {
+        let Some(keyword) = statement.split_whitespace().next() else {
+            continue;
+        };
+        match keyword {
+            "module" => {
+                if let Some((_, ports)) = statement.split_once('(') {
+                    let ports = ports.trim_end().trim_end_matches(')');
+                    if ports.contains("input") || ports.contains("output") {
+                        declare(&mut netlist, ports)?;
+                    }
+                }
+            }
+            "input" | "output" | "wire" => declare(&mut netlist, statement)?,
+            "assign" => {
+                let Some((name, expression)) = statement["assign".len()..].split_once('=') else {
+                    return Err(format!("`{statement}` is not an assignment"));
+                };
+                assign(&mut netlist, name, expression)?;
+            }
+            gate => {
+                let connections = statement
+                    .split_once('(')
+                    .map(|(_, connections)| connections.trim_end().trim_end_matches(')'));
+                let connections = connections
+                    .map(|connections| connections.split(',').map(str::trim).collect::<Vec<_>>());
+                let Some(connections) = connections.filter(|connections| connections.len() > 1)
+                else {
+                    return Err(format!("`{statement}` is not supported"));
+                };
+                // `buf` and `not` drive all their terminals but the last one,
+                // the other gates only their first one.
+                let (outputs, inputs) = match gate {
+                    "buf" | "not" => connections.split_at(connections.len() - 1),
+                    _ => connections.split_at(1),
+                };
+                let Some(expr) = primitive(gate, inputs) else {
+                    return Err(format!("`{statement}` is not supported"));
+                };
+                for output in outputs {
+                    netlist.define(output, expr.clone())?;
+                }
+            }
+        }
+    }
+    Ok(netlist)
+}
+
+fn verilog_commands(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    design: Design,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        if command.name != "verilog" {
+            continue;
+        }
+
+        let (path, module) = match command.args().as_slice() {
+            [path] => {
+                let stem = Path::new(path).file_stem().and_then(|stem| stem.to_str());
+                (path.to_string(), stem.unwrap_or("circuit").to_string())
+            }
+            [path, module] => (path.to_string(), module.to_string()),
+            _ => {
+                console.print("usage: verilog <path> [module]");
+                continue;
+            }
+        };
+
+        let result = design
+            .to_verilog(&module)
+            .and_then(|verilog| fs::write(&path, verilog).map_err(|error| error.to_string()));
+        match result {
+            Ok(()) => console.print(format!("wrote {path}")),
+            Err(error) => console.print(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    #[test]
+    fn single_bits_are_written_in_binary() {
+        assert_eq!(literal(Signal::bit(true)), "1'b1");
+        assert_eq!(literal(Signal::bit(false)), "1'b0");
+        assert_eq!(literal(Signal::new(8, 0x2a)), "8'h2a");
+        assert_eq!(literal(Signal::undefined(2)), "2'bxx");
+    }
+
+    #[test]
+    fn comments_are_dropped() {
+        assert_eq!(
+            strip_comments("a // line\nb /* block\n */ c /* open"),
+            "a  \nb   c  "
+        );
+    }
+
+    #[test]
+    fn gate_primitives_compute_their_function() {
+        let netlist = parse(
+            "module m (input a, input b, output y0, output y1, output y2, output y3, output y4,\n\
+             output y5, output y6);\n\
+             and g0 (y0, a, b); nand (y1, a, b); or (y2, a, b); nor (y3, a, b);\n\
+             xor (y4, a, b); xnor (y5, a, b); buf (y6, a);\n\
+             endmodule",
+        )
+        .unwrap();
+        let functions: [fn(bool, bool) -> bool; 7] = [
+            |a, b| a && b,
+            |a, b| !(a && b),
+            |a, b| a || b,
+            |a, b| !(a || b),
+            |a, b| a != b,
+            |a, b| a == b,
+            |a, _| a,
+        ];
+        for (index, function) in functions.iter().enumerate() {
+            for (a, b) in [(false, false), (false, true), (true, false), (true, true)] {
+                let output = format!("y{index}");
+                let value = netlist.evaluate(&output, &[("a", a), ("b", b)]);
+                assert_eq!(value, function(a, b), "{output} for {a} {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn signals_are_driven_once() {
+        let text = "module m (input a, output y); assign y = a; not (y, a); endmodule";
+        assert!(parse(text).is_err());
+        assert!(parse("module m (input a, output y); nor3 (y, a); endmodule").is_err());
+    }
+
+    #[test]
+    fn declarations_assign_their_wires() {
+        let netlist = parse(
+            "module m (a, b, y); input a, b; output y;\n\
+             wire t = a & b, u = ~t; assign y = u; endmodule",
+        )
+        .unwrap();
+        assert_eq!(netlist.inputs, ["a", "b"]);
+        assert_eq!(netlist.outputs, ["y"]);
+        assert!(!netlist.evaluate("y", &[("a", true), ("b", true)]));
+        assert!(netlist.evaluate("y", &[("a", true), ("b", false)]));
+    }
+
+    #[test]
+    fn buf_and_not_drive_every_terminal_but_the_last() {
+        let netlist = parse(
+            "module m (input a, output y, output z); // fan out\n\
+             not (y, z, a); endmodule",
+        )
+        .unwrap();
+        for name in ["y", "z"] {
+            assert!(!netlist.evaluate(name, &[("a", true)]));
+            assert!(netlist.evaluate(name, &[("a", false)]));
+        }
+        assert!(parse("module m (input a); and (a); endmodule").is_err());
+    }
+
+    #[test]
+    fn unsupported_operators_are_rejected() {
+        assert!(parse("module m (input a, output y); assign y = a + a; endmodule").is_err());
+        assert!(parse("module m (input a); wire [1:0] b; endmodule").is_err());
+    }
+
+    #[test]
+    fn exported_modules_are_read_back() {
+        let mut world = World::new();
+        let button = world.spawn(LogicButton::default()).id();
+        let power = world.spawn(Constant::Power).id();
+        world.spawn((
+            Gate::And(Some(button), Some(power)),
+            ItemLabel("y".to_string()),
+        ));
+
+        let verilog = world
+            .run_system_once(|design: Design| design.to_verilog("top"))
+            .unwrap()
+            .unwrap();
+        let netlist = parse(&verilog).unwrap();
+        assert_eq!(netlist.inputs.len(), 1);
+        assert_eq!(netlist.outputs, ["y"]);
+        let input = netlist.inputs[0].as_str();
+        for level in [false, true] {
+            assert_eq!(netlist.evaluate("y", &[(input, level)]), level);
+        }
+    }
+}
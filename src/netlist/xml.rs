@@ -0,0 +1,153 @@
+/// An XML element with its attributes and child elements, text being
+/// ignored. Enough to read the files of other circuit editors.
+#[derive(Default, Debug)]
+pub struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Element>,
+}
+
+impl Element {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(other, _)| other == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Reads the attributes of a start tag, after its name.
+fn attributes(mut text: &str) -> Result<Vec<(String, String)>, String> {
+    let mut attributes = Vec::new();
+    loop {
+        text = text.trim_start();
+        if text.is_empty() {
+            return Ok(attributes);
+        }
+        let (name, rest) = text
+            .split_once('=')
+            .ok_or_else(|| format!("attribute without a value in `{text}`"))?;
+        let rest = rest.trim_start();
+        let quote = rest
+            .chars()
+            .next()
+            .filter(|quote| *quote == '"' || *quote == '\'')
+            .ok_or_else(|| format!("unquoted attribute `{}`", name.trim()))?;
+        let (value, rest) = rest[1..]
+            .split_once(quote)
+            .ok_or_else(|| format!("unterminated attribute `{}`", name.trim()))?;
+        attributes.push((name.trim().to_string(), unescape(value)));
+        text = rest;
+    }
+}
+
+/// Parses a document into its root element.
+pub fn parse(text: &str) -> Result<Element, String> {
+    // The elements whose end tag has not been read yet, the outermost first.
+    let mut open = vec![Element::default()];
+    let mut rest = text;
+
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        let (end_marker, skip) = if rest.starts_with("<!--") {
+            ("-->", true)
+        } else if rest.starts_with("<?") || rest.starts_with("<!") {
+            (">", true)
+        } else {
+            (">", false)
+        };
+        let end = rest
+            .find(end_marker)
+            .ok_or_else(|| "unterminated tag".to_string())?;
+        let tag = &rest[1..end];
+        rest = &rest[end + end_marker.len()..];
+        if skip {
+            continue;
+        }
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let element = open.pop().filter(|element| element.name == name.trim());
+            let (Some(element), Some(parent)) = (element, open.last_mut()) else {
+                return Err(format!("unexpected </{}>", name.trim()));
+            };
+            parent.children.push(element);
+            continue;
+        }
+
+        let (tag, closed) = match tag.strip_suffix('/') {
+            Some(tag) => (tag, true),
+            None => (tag, false),
+        };
+        let (name, attributes_text) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+        let element = Element {
+            name: name.to_string(),
+            attributes: attributes(attributes_text)?,
+            children: Vec::new(),
+        };
+        match (closed, open.last_mut()) {
+            (true, Some(parent)) => parent.children.push(element),
+            _ => open.push(element),
+        }
+    }
+
+    match (open.pop(), open.is_empty()) {
+        (Some(mut document), true) => document
+            .children
+            .pop()
+            .ok_or_else(|| "empty document".to_string()),
+        (Some(element), false) => Err(format!("<{}> is not closed", element.name)),
+        (None, _) => Err("empty document".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elements_nest_with_their_attributes() {
+        let document = parse(
+            "<?xml version=\"1.0\"?>\n<!-- a <comment> -->\n<!DOCTYPE p>\n\
+             <p a=\"1\" b = 'x &amp; &lt;y&gt;'>text<c/><d n=\"2\"><c/></d><c k=\"&quot;\"/></p>",
+        )
+        .unwrap();
+        assert_eq!(document.name, "p");
+        assert_eq!(document.attribute("a"), Some("1"));
+        assert_eq!(document.attribute("b"), Some("x & <y>"));
+        assert_eq!(document.attribute("z"), None);
+        assert_eq!(document.children("c").count(), 2);
+        let d = document.children("d").next().unwrap();
+        assert_eq!(d.attribute("n"), Some("2"));
+        assert_eq!(d.children.len(), 1);
+        let last = document.children("c").last().unwrap();
+        assert_eq!(last.attribute("k"), Some("\""));
+    }
+
+    #[test]
+    fn malformed_documents_are_rejected() {
+        for text in [
+            "",
+            "<p>",
+            "<p></q>",
+            "</p>",
+            "<p a></p>",
+            "<p a=1></p>",
+            "<p a=\"1></p>",
+            "<p",
+        ] {
+            assert!(parse(text).is_err(), "{text:?}");
+        }
+    }
+}
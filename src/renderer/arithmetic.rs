@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+
+use crate::{logic::arithmetic::Arithmetic, renderer::spawn_pin_labels};
+
+pub struct ArithmeticRendererPlugin;
+impl Plugin for ArithmeticRendererPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, display_arithmetic);
+    }
+}
+
+fn display_arithmetic(
+    new_components: Query<(Entity, &Arithmetic), Added<Arithmetic>>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (entity, arithmetic) in new_components.iter() {
+        let size = arithmetic.size();
+        let mut entity = commands.entity(entity);
+        entity
+            .insert((
+                Mesh2d(meshes.add(Mesh::from(Rectangle::from_size(size)))),
+                MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(0.5, 0.5, 0.5)))),
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text2d::new(arithmetic.symbol()),
+                    TextFont::from_font_size(10.0),
+                    TextColor(Color::BLACK),
+                    Transform::from_xyz(0.0, 0.0, 0.1),
+                ));
+            });
+        spawn_pin_labels(
+            &mut entity,
+            size,
+            arithmetic.input_names(),
+            arithmetic.output_names(),
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+
+use crate::logic::{
+    OutputPin, PIN_RADIUS,
+    bus::{Merger, Splitter},
+};
+
+pub struct BusRendererPlugin;
+impl Plugin for BusRendererPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (display_splitters, display_mergers, display_output_pins),
+        );
+    }
+}
+
+fn display_splitters(
+    new_splitters: Query<(Entity, &Splitter), Added<Splitter>>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (entity, splitter) in new_splitters.iter() {
+        commands.entity(entity).insert((
+            Mesh2d(meshes.add(Mesh::from(Rectangle::from_size(splitter.size())))),
+            MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(0.5, 0.5, 0.5)))),
+        ));
+    }
+}
+
+fn display_mergers(
+    new_mergers: Query<(Entity, &Merger), Added<Merger>>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (entity, merger) in new_mergers.iter() {
+        commands.entity(entity).insert((
+            Mesh2d(meshes.add(Mesh::from(Rectangle::from_size(merger.size())))),
+            MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(0.5, 0.5, 0.5)))),
+        ));
+    }
+}
+
+fn display_output_pins(
+    new_pins: Query<Entity, Added<OutputPin>>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for entity in new_pins.iter() {
+        commands.entity(entity).insert((
+            Mesh2d(meshes.add(Mesh::from(Circle { radius: PIN_RADIUS }))),
+            MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(0.5, 0.5, 0.5)))),
+        ));
+    }
+}
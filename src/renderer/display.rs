@@ -0,0 +1,117 @@
+use bevy::prelude::*;
+
+use crate::{
+    logic::display::{SegmentDisplay, Segments},
+    renderer::spawn_pin_labels,
+    signal::Level,
+};
+
+const SEGMENT_LENGTH: f32 = 14.0;
+const SEGMENT_THICKNESS: f32 = 3.0;
+
+pub struct DisplayRendererPlugin;
+impl Plugin for DisplayRendererPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_segment_meshes)
+            .add_systems(Update, (display_segment_displays, update_segments));
+    }
+}
+
+/// The meshes shared by the segments of every display.
+#[derive(Resource)]
+struct SegmentMeshes {
+    horizontal: Handle<Mesh>,
+    vertical: Handle<Mesh>,
+}
+
+/// Segment `0` to `6` (`a` to `g`) of a display.
+#[derive(Component)]
+struct Segment(usize);
+
+/// Center of each segment, and whether it is horizontal.
+fn segment_layout(segment: usize) -> (Vec2, bool) {
+    let x = SEGMENT_LENGTH / 2.0;
+    let y = SEGMENT_LENGTH;
+    match segment {
+        0 => (Vec2::new(0.0, y), true),
+        1 => (Vec2::new(x, y / 2.0), false),
+        2 => (Vec2::new(x, -y / 2.0), false),
+        3 => (Vec2::new(0.0, -y), true),
+        4 => (Vec2::new(-x, -y / 2.0), false),
+        5 => (Vec2::new(-x, y / 2.0), false),
+        _ => (Vec2::ZERO, true),
+    }
+}
+
+fn segment_color(level: Level) -> Color {
+    match level {
+        Level::High => Color::srgb(1.0, 0.1, 0.1),
+        Level::Low => Color::srgb(0.25, 0.05, 0.05),
+        Level::Undefined => Color::srgb(0.6, 0.6, 0.6),
+        Level::HighZ => Color::srgb(0.2, 0.4, 1.0),
+    }
+}
+
+fn setup_segment_meshes(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
+    commands.insert_resource(SegmentMeshes {
+        horizontal: meshes.add(Mesh::from(Rectangle::new(
+            SEGMENT_LENGTH,
+            SEGMENT_THICKNESS,
+        ))),
+        vertical: meshes.add(Mesh::from(Rectangle::new(
+            SEGMENT_THICKNESS,
+            SEGMENT_LENGTH,
+        ))),
+    });
+}
+
+fn display_segment_displays(
+    new_displays: Query<(Entity, &SegmentDisplay, &Segments), Added<SegmentDisplay>>,
+    mut commands: Commands,
+    segment_meshes: Res<SegmentMeshes>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (entity, display, segments) in new_displays.iter() {
+        let size = display.size();
+        let mut entity = commands.entity(entity);
+        entity
+            .insert((
+                Mesh2d(meshes.add(Mesh::from(Rectangle::from_size(size)))),
+                MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(0.5, 0.5, 0.5)))),
+            ))
+            .with_children(|parent| {
+                for (index, level) in segments.0.iter().enumerate() {
+                    let (center, is_horizontal) = segment_layout(index);
+                    let mesh = if is_horizontal {
+                        &segment_meshes.horizontal
+                    } else {
+                        &segment_meshes.vertical
+                    };
+                    parent.spawn((
+                        Segment(index),
+                        Mesh2d(mesh.clone()),
+                        MeshMaterial2d(materials.add(ColorMaterial::from(segment_color(*level)))),
+                        Transform::from_translation(center.extend(0.1)),
+                    ));
+                }
+            });
+        spawn_pin_labels(&mut entity, size, display.input_names(), &[]);
+    }
+}
+
+fn update_segments(
+    displays: Query<(&Segments, &Children), Changed<Segments>>,
+    segments: Query<(&Segment, &MeshMaterial2d<ColorMaterial>)>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (levels, children) in displays.iter() {
+        for child in children.iter() {
+            if let Ok((segment, handle)) = segments.get(child)
+                && let Some(material) = materials.get_mut(handle)
+            {
+                material.color = segment_color(levels.0[segment.0]);
+            }
+        }
+    }
+}
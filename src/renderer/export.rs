@@ -0,0 +1,486 @@
+use std::fs;
+
+use bevy::{
+    camera::{RenderTarget, ScalingMode, visibility::RenderLayers},
+    ecs::system::SystemParam,
+    mesh::{PrimitiveTopology, VertexAttributeValues},
+    prelude::*,
+    render::{
+        render_resource::TextureFormat,
+        view::screenshot::{Screenshot, ScreenshotCaptured, save_to_disk},
+    },
+    sprite::Anchor,
+};
+
+use crate::{
+    console::{Console, ConsoleAppExt, ConsoleCommand},
+    grid::BASE_SPACING,
+    link::Link,
+    renderer::shadow::ShadowEntity,
+    selection::Selected,
+};
+
+/// Space left around the exported items.
+const MARGIN: f32 = 20.0;
+/// Largest side of a PNG export, in pixels.
+const MAX_PNG_SIZE: u32 = 8192;
+/// Frames left to the renderer to prepare the target of a PNG export before
+/// capturing it.
+const PNG_FRAMES: u8 = 2;
+/// Layer the selection is drawn on while being exported to a PNG, for the
+/// export camera to only see it.
+const EXPORT_LAYER: usize = 2;
+
+pub struct ExportRendererPlugin;
+impl Plugin for ExportRendererPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_console_command(
+            "svg",
+            "svg <path> [selection] [grid], export the circuit or the selection as SVG",
+        )
+        .add_console_command(
+            "png",
+            "png <path> <width> <height> [selection], render the circuit or the selection to a PNG",
+        )
+        .add_systems(Update, (export_commands, capture_exports));
+    }
+}
+
+/// A camera rendering a PNG export offscreen, until its image is captured.
+#[derive(Component)]
+struct ExportCamera {
+    image: Handle<Image>,
+    path: String,
+    /// Frames to wait before the capture, `None` once it is requested.
+    frames: Option<u8>,
+    /// The entities put on the export layer, to take back once captured.
+    layered: Vec<Entity>,
+}
+
+/// Something drawn on the canvas, in world coordinates.
+enum Shape {
+    Triangles(Vec<[Vec2; 3]>, Color),
+    Lines(Vec<[Vec2; 2]>, Color),
+    Text {
+        position: Vec2,
+        text: String,
+        size: f32,
+        color: Color,
+        anchor: Vec2,
+    },
+}
+
+impl Shape {
+    fn points(&self) -> Vec<Vec2> {
+        match self {
+            Shape::Triangles(triangles, _) => triangles.iter().flatten().copied().collect(),
+            Shape::Lines(lines, _) => lines.iter().flatten().copied().collect(),
+            Shape::Text { position, .. } => vec![*position],
+        }
+    }
+}
+
+/// The visible content of the canvas, as drawn by the renderer.
+#[derive(SystemParam)]
+#[allow(clippy::type_complexity)]
+struct Canvas<'w, 's> {
+    meshes: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Mesh2d,
+            &'static MeshMaterial2d<ColorMaterial>,
+            &'static GlobalTransform,
+            &'static InheritedVisibility,
+        ),
+        Without<ShadowEntity>,
+    >,
+    texts: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Text2d,
+            &'static TextFont,
+            Option<&'static TextColor>,
+            Option<&'static Anchor>,
+            &'static GlobalTransform,
+            &'static InheritedVisibility,
+        ),
+    >,
+    mesh_assets: Res<'w, Assets<Mesh>>,
+    materials: Res<'w, Assets<ColorMaterial>>,
+    selected: Query<'w, 's, (), With<Selected>>,
+    links: Query<'w, 's, &'static Link>,
+    parents: Query<'w, 's, &'static ChildOf>,
+    clear_color: Res<'w, ClearColor>,
+}
+
+impl Canvas<'_, '_> {
+    /// Whether `entity` belongs to a selected item, or is part of a link
+    /// between two selected items.
+    fn in_selection(&self, entity: Entity) -> bool {
+        let mut current = Some(entity);
+        while let Some(entity) = current {
+            if self.selected.contains(entity) {
+                return true;
+            }
+            if let Ok(link) = self.links.get(entity) {
+                return self.selected.contains(link.from) && self.selected.contains(link.to);
+            }
+            current = self.parents.get(entity).ok().map(ChildOf::parent);
+        }
+        false
+    }
+
+    /// The visible entities drawing the selection.
+    fn selection_entities(&self) -> Vec<Entity> {
+        let meshes = self
+            .meshes
+            .iter()
+            .map(|(entity, .., visibility)| (entity, visibility));
+        let texts = self
+            .texts
+            .iter()
+            .map(|(entity, .., visibility)| (entity, visibility));
+        meshes
+            .chain(texts)
+            .filter(|(entity, visibility)| visibility.get() && self.in_selection(*entity))
+            .map(|(entity, _)| entity)
+            .collect()
+    }
+
+    /// The shapes to draw, from the farthest to the nearest.
+    fn shapes(&self, selection: bool) -> Vec<Shape> {
+        let mut shapes = Vec::new();
+        let included = |entity: Entity, visibility: &InheritedVisibility| {
+            visibility.get() && (!selection || self.in_selection(entity))
+        };
+
+        for (entity, mesh, material, transform, visibility) in self.meshes.iter() {
+            let (Some(mesh), Some(material)) = (
+                self.mesh_assets.get(&mesh.0),
+                self.materials.get(&material.0),
+            ) else {
+                continue;
+            };
+            if !included(entity, visibility) {
+                continue;
+            }
+            let Some(VertexAttributeValues::Float32x3(positions)) =
+                mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+            else {
+                continue;
+            };
+
+            let points = positions
+                .iter()
+                .map(|position| transform.transform_point(Vec3::from(*position)).truncate())
+                .collect::<Vec<_>>();
+            let indices = match mesh.indices() {
+                Some(indices) => indices.iter().collect::<Vec<_>>(),
+                None => (0..points.len()).collect(),
+            };
+            let vertex = |index: &usize| points.get(*index).copied().unwrap_or_default();
+            let shape = match mesh.primitive_topology() {
+                PrimitiveTopology::TriangleList => Shape::Triangles(
+                    indices
+                        .chunks_exact(3)
+                        .map(|triangle| {
+                            [
+                                vertex(&triangle[0]),
+                                vertex(&triangle[1]),
+                                vertex(&triangle[2]),
+                            ]
+                        })
+                        .collect(),
+                    material.color,
+                ),
+                PrimitiveTopology::LineList => Shape::Lines(
+                    indices
+                        .chunks_exact(2)
+                        .map(|line| [vertex(&line[0]), vertex(&line[1])])
+                        .collect(),
+                    material.color,
+                ),
+                _ => continue,
+            };
+            shapes.push((transform.translation().z, shape));
+        }
+
+        for (entity, text, font, color, anchor, transform, visibility) in self.texts.iter() {
+            if text.0.is_empty() || !included(entity, visibility) {
+                continue;
+            }
+            let shape = Shape::Text {
+                position: transform.translation().truncate(),
+                text: text.0.clone(),
+                size: font.font_size * transform.scale().y,
+                color: color.map_or(Color::WHITE, |color| color.0),
+                anchor: anchor.map_or(Vec2::ZERO, |anchor| anchor.0),
+            };
+            shapes.push((transform.translation().z, shape));
+        }
+
+        shapes.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        shapes.into_iter().map(|(_, shape)| shape).collect()
+    }
+
+    /// The rectangle holding all the shapes with a margin, if there are any.
+    fn bounds(shapes: &[Shape]) -> Option<Rect> {
+        let points = shapes.iter().flat_map(Shape::points);
+        let bounds = points.fold(None, |bounds: Option<Rect>, point| {
+            Some(
+                bounds.map_or(Rect::from_center_size(point, Vec2::ZERO), |bounds| {
+                    bounds.union_point(point)
+                }),
+            )
+        })?;
+        Some(bounds.inflate(MARGIN))
+    }
+
+    fn to_svg(&self, selection: bool, grid: bool) -> Result<String, String> {
+        let shapes = self.shapes(selection);
+        let Some(bounds) = Self::bounds(&shapes) else {
+            return Err("nothing to export".to_string());
+        };
+
+        // SVG has y pointing down.
+        let flip = |point: Vec2| Vec2::new(point.x, -point.y);
+        let top_left = flip(Vec2::new(bounds.min.x, bounds.max.y));
+        let size = bounds.size();
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\" width=\"{}\" height=\"{}\">\n",
+            top_left.x, top_left.y, size.x, size.y, size.x, size.y
+        );
+        svg.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" {}/>\n",
+            top_left.x,
+            top_left.y,
+            size.x,
+            size.y,
+            paint("fill", self.clear_color.0)
+        ));
+
+        if grid {
+            let dot = paint("fill", Color::srgba(0.5, 0.5, 0.5, 0.4));
+            let mut x = (bounds.min.x / BASE_SPACING).ceil() * BASE_SPACING;
+            while x <= bounds.max.x {
+                let mut y = (bounds.min.y / BASE_SPACING).ceil() * BASE_SPACING;
+                while y <= bounds.max.y {
+                    svg.push_str(&format!(
+                        "<circle cx=\"{x}\" cy=\"{}\" r=\"1.5\" {dot}/>\n",
+                        -y
+                    ));
+                    y += BASE_SPACING;
+                }
+                x += BASE_SPACING;
+            }
+        }
+
+        for shape in &shapes {
+            match shape {
+                Shape::Triangles(triangles, color) => {
+                    let path = triangles
+                        .iter()
+                        .map(|[a, b, c]| {
+                            let [a, b, c] = [flip(*a), flip(*b), flip(*c)];
+                            format!("M{} {}L{} {}L{} {}Z", a.x, a.y, b.x, b.y, c.x, c.y)
+                        })
+                        .collect::<String>();
+                    // A thin stroke of the same colour hides the seams
+                    // between the triangles.
+                    svg.push_str(&format!(
+                        "<path d=\"{path}\" {} {} stroke-width=\"0.5\" stroke-linejoin=\"round\"/>\n",
+                        paint("fill", *color),
+                        paint("stroke", *color)
+                    ));
+                }
+                Shape::Lines(lines, color) => {
+                    let path = lines
+                        .iter()
+                        .map(|[a, b]| {
+                            let [a, b] = [flip(*a), flip(*b)];
+                            format!("M{} {}L{} {}", a.x, a.y, b.x, b.y)
+                        })
+                        .collect::<String>();
+                    svg.push_str(&format!(
+                        "<path d=\"{path}\" fill=\"none\" {} stroke-width=\"1\"/>\n",
+                        paint("stroke", *color)
+                    ));
+                }
+                Shape::Text {
+                    position,
+                    text,
+                    size,
+                    color,
+                    anchor,
+                } => {
+                    let position = flip(*position);
+                    let text_anchor = match anchor.x {
+                        x if x < 0.0 => "start",
+                        x if x > 0.0 => "end",
+                        _ => "middle",
+                    };
+                    let lines = text.lines().collect::<Vec<_>>();
+                    // Offset of the first baseline, in lines, for the block
+                    // of lines to sit on its anchor.
+                    let first = match anchor.y {
+                        y if y > 0.0 => 1.0,
+                        y if y < 0.0 => 1.0 - lines.len() as f32,
+                        _ => 0.5 - lines.len() as f32 / 2.0 + 0.35,
+                    };
+                    svg.push_str(&format!(
+                        "<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"{size}\" \
+                         text-anchor=\"{text_anchor}\" {}>",
+                        position.x,
+                        position.y,
+                        paint("fill", *color)
+                    ));
+                    for (index, line) in lines.iter().enumerate() {
+                        let dy = if index == 0 { first } else { 1.0 };
+                        svg.push_str(&format!(
+                            "<tspan x=\"{}\" dy=\"{dy}em\">{}</tspan>",
+                            position.x,
+                            escape(line)
+                        ));
+                    }
+                    svg.push_str("</text>\n");
+                }
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        Ok(svg)
+    }
+}
+
+/// The SVG attributes painting `property` with `color`.
+fn paint(property: &str, color: Color) -> String {
+    let color = color.to_srgba();
+    let hex = color.with_alpha(1.0).to_hex();
+    match color.alpha {
+        1.0 => format!("{property}=\"{hex}\""),
+        alpha => format!("{property}=\"{hex}\" {property}-opacity=\"{alpha:.2}\""),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn export_commands(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    canvas: Canvas,
+    mut images: ResMut<Assets<Image>>,
+    mut commands: Commands,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        let args = command.args();
+        let result = match (command.name.as_str(), args.as_slice()) {
+            ("svg", [path, options @ ..]) => {
+                let selection = options.contains(&"selection");
+                let grid = options.contains(&"grid");
+                canvas.to_svg(selection, grid).and_then(|svg| {
+                    fs::write(path, svg).map_err(|error| format!("cannot write {path}: {error}"))
+                })
+            }
+            ("svg", _) => Err("usage: svg <path> [selection] [grid]".to_string()),
+            ("png", [path, width, height, options @ ..]) => {
+                let size = (width.parse::<u32>(), height.parse::<u32>());
+                let (Ok(width @ 1..=MAX_PNG_SIZE), Ok(height @ 1..=MAX_PNG_SIZE)) = size else {
+                    console.print(format!(
+                        "the size must be between 1 and {MAX_PNG_SIZE} pixels"
+                    ));
+                    continue;
+                };
+                let selection = options.contains(&"selection");
+                let shapes = canvas.shapes(selection);
+                match Canvas::bounds(&shapes) {
+                    Some(bounds) => {
+                        // A selection export only renders the export layer,
+                        // which the selected entities join meanwhile.
+                        let (layers, layered) = match selection {
+                            true => (
+                                RenderLayers::layer(EXPORT_LAYER),
+                                canvas.selection_entities(),
+                            ),
+                            false => (RenderLayers::default(), vec![]),
+                        };
+                        for entity in &layered {
+                            commands
+                                .entity(*entity)
+                                .insert(RenderLayers::from_layers(&[0, EXPORT_LAYER]));
+                        }
+                        let image =
+                            Image::new_target_texture(width, height, TextureFormat::Rgba8UnormSrgb);
+                        let image = images.add(image);
+                        commands.spawn((
+                            Camera2d,
+                            Camera {
+                                target: RenderTarget::Image(image.clone().into()),
+                                order: -1,
+                                clear_color: ClearColorConfig::Custom(canvas.clear_color.0),
+                                ..default()
+                            },
+                            Projection::from(OrthographicProjection {
+                                scaling_mode: ScalingMode::AutoMin {
+                                    min_width: bounds.width(),
+                                    min_height: bounds.height(),
+                                },
+                                ..OrthographicProjection::default_2d()
+                            }),
+                            Transform::from_translation(bounds.center().extend(0.0)),
+                            layers,
+                            ExportCamera {
+                                image,
+                                path: path.to_string(),
+                                frames: Some(PNG_FRAMES),
+                                layered,
+                            },
+                        ));
+                        Ok(())
+                    }
+                    None => Err("nothing to export".to_string()),
+                }
+            }
+            ("png", _) => Err("usage: png <path> <width> <height> [selection]".to_string()),
+            _ => continue,
+        };
+
+        match result {
+            Ok(()) => console.print(format!("exporting to {}", args[0])),
+            Err(error) => console.print(error),
+        }
+    }
+}
+
+/// Captures the image of each export camera once it had time to render,
+/// then removes the camera and takes the exported entities off its layer.
+fn capture_exports(mut cameras: Query<(Entity, &mut ExportCamera)>, mut commands: Commands) {
+    for (entity, mut camera) in cameras.iter_mut() {
+        let Some(frames) = camera.frames else {
+            continue;
+        };
+        if frames > 0 {
+            camera.frames = Some(frames - 1);
+            continue;
+        }
+
+        camera.frames = None;
+        let layered = std::mem::take(&mut camera.layered);
+        commands
+            .spawn(Screenshot::image(camera.image.clone()))
+            .observe(save_to_disk(camera.path.clone()))
+            .observe(move |_: On<ScreenshotCaptured>, mut commands: Commands| {
+                commands.entity(entity).despawn();
+                for layered in &layered {
+                    commands.entity(*layered).try_remove::<RenderLayers>();
+                }
+            });
+    }
+}
@@ -1,4 +1,7 @@
-use crate::logic::*;
+use crate::{
+    logic::{plexer::Plexer, *},
+    signal::Signal,
+};
 
 use bevy::{
     asset::RenderAssetUsages,
@@ -9,7 +12,19 @@ use bevy::{
 pub struct GateRendererPlugin;
 impl Plugin for GateRendererPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (display_gates, update_gate_colors, display_buttons));
+        app.add_systems(
+            Update,
+            (
+                display_gates,
+                update_gate_colors,
+                display_buttons,
+                update_button_keys,
+                display_labels,
+                display_junctions,
+                display_tunnels,
+                display_plexers,
+            ),
+        );
     }
 }
 
@@ -21,6 +36,7 @@ impl Gate {
             Gate::And(_, _) => create_and_mesh(50.0, 40.0, resolution),
             Gate::Or(_, _) => create_or_mesh(50.0, 40.0, resolution),
             Gate::Not(_) => create_not_mesh(40.0, 30.0, resolution),
+            Gate::TriState(_, _) => create_tri_state_mesh(40.0, 30.0),
         }
     }
 }
@@ -154,7 +170,59 @@ fn create_not_mesh(width: f32, height: f32, resolution: u32) -> Mesh {
     generate_mesh(positions, indices)
 }
 
-fn generate_mesh(positions: Vec<[f32; 3]>, indices: Vec<u32>) -> Mesh {
+fn create_tri_state_mesh(width: f32, height: f32) -> Mesh {
+    let left_x = -width / 2.0;
+    let tip_x = width / 2.0;
+    let stub_width = 3.0;
+    let stub_top = height / 2.0;
+
+    let positions = vec![
+        [left_x, height / 2.0, 0.0],
+        [left_x, -height / 2.0, 0.0],
+        [tip_x, 0.0, 0.0],
+        // The enable input, sticking out of the top side.
+        [-stub_width / 2.0, 0.0, 0.0],
+        [stub_width / 2.0, 0.0, 0.0],
+        [stub_width / 2.0, stub_top, 0.0],
+        [-stub_width / 2.0, stub_top, 0.0],
+    ];
+    let indices = vec![0, 1, 2, 3, 4, 5, 3, 5, 6];
+
+    generate_mesh(positions, indices)
+}
+
+/// A trapezoid centered on the origin whose left and right sides have the
+/// given heights.
+fn create_trapezoid_mesh(width: f32, left_height: f32, right_height: f32) -> Mesh {
+    let positions = vec![
+        [-width / 2.0, left_height / 2.0, 0.0],
+        [-width / 2.0, -left_height / 2.0, 0.0],
+        [width / 2.0, -right_height / 2.0, 0.0],
+        [width / 2.0, right_height / 2.0, 0.0],
+    ];
+    let indices = vec![0, 1, 2, 0, 2, 3];
+
+    generate_mesh(positions, indices)
+}
+
+fn create_tunnel_mesh(width: f32, height: f32) -> Mesh {
+    let left_x = -width / 2.0;
+    let right_x = width / 2.0;
+    let shoulder_x = right_x - height / 2.0;
+
+    let positions = vec![
+        [left_x, height / 2.0, 0.0],
+        [left_x, -height / 2.0, 0.0],
+        [shoulder_x, -height / 2.0, 0.0],
+        [right_x, 0.0, 0.0],
+        [shoulder_x, height / 2.0, 0.0],
+    ];
+    let indices = vec![0, 1, 2, 0, 2, 4, 4, 2, 3];
+
+    generate_mesh(positions, indices)
+}
+
+pub(crate) fn generate_mesh(positions: Vec<[f32; 3]>, indices: Vec<u32>) -> Mesh {
     let mut mesh = Mesh::new(
         PrimitiveTopology::TriangleList,
         RenderAssetUsages::default(),
@@ -180,44 +248,215 @@ pub fn display_gates(
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub fn update_gate_colors(
     mut materials: ResMut<Assets<ColorMaterial>>,
-    query: Query<(&Value, &MeshMaterial2d<ColorMaterial>), Changed<Value>>,
+    query: Query<(&Value, &MeshMaterial2d<ColorMaterial>, Has<Contention>), Changed<Value>>,
     all_query: Query<&MeshMaterial2d<ColorMaterial>, With<Item>>,
     mut removed_values: RemovedComponents<Value>,
 ) {
-    for (value, mat_handle) in query.iter() {
+    for (value, mat_handle, contention) in query.iter() {
         if let Some(material) = materials.get_mut(mat_handle) {
-            material.color = if value.state {
-                Color::srgb(0.0, 1.0, 0.0)
+            material.color = if contention {
+                Color::srgb(1.0, 0.0, 1.0)
             } else {
-                Color::srgb(1.0, 0.0, 0.0)
+                signal_color(&value.state)
             };
         }
     }
 
     for entity in removed_values.read() {
-        if let Ok(mat_handle) = all_query.get(entity) {
-            if let Some(material) = materials.get_mut(mat_handle) {
-                material.color = Color::srgb(0.5, 0.5, 0.5);
-            }
+        if let Ok(mat_handle) = all_query.get(entity)
+            && let Some(material) = materials.get_mut(mat_handle)
+        {
+            material.color = Color::srgb(0.5, 0.5, 0.5);
         }
     }
 }
 
+/// Green for 1, red for 0, grey when undefined and blue when floating. A bus
+/// is green as soon as one of its bits is 1.
+pub fn signal_color(signal: &Signal) -> Color {
+    if signal.is_undefined() {
+        Color::srgb(0.5, 0.5, 0.5)
+    } else if signal.is_floating() {
+        Color::srgb(0.2, 0.4, 1.0)
+    } else if signal.is_high() {
+        Color::srgb(0.0, 1.0, 0.0)
+    } else {
+        Color::srgb(1.0, 0.0, 0.0)
+    }
+}
+
 pub fn display_buttons(
     new_buttons: Query<(Entity, &LogicButton), Added<LogicButton>>,
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
-    for (entity, _) in new_buttons.iter() {
+    for (entity, button) in new_buttons.iter() {
+        let mesh = match button.kind {
+            ButtonKind::Toggle => Mesh::from(Circle { radius: 10.0 }),
+            ButtonKind::Momentary => Mesh::from(Rectangle::from_length(20.0)),
+        };
+        commands
+            .entity(entity)
+            .insert((
+                Mesh2d(meshes.add(mesh)),
+                MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(1.0, 0., 0.)))),
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Mesh2d(meshes.add(Mesh::from(Circle {
+                        radius: KNOB_RADIUS,
+                    }))),
+                    MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(0.3, 0.3, 0.3)))),
+                    Transform::from_xyz(0.0, 0.0, 0.1),
+                ));
+                parent.spawn((
+                    ButtonKeyLabel,
+                    Text2d::new(""),
+                    TextFont::from_font_size(8.0),
+                    Transform::from_xyz(0.0, 16.0, 0.1),
+                ));
+            });
+    }
+}
+
+/// Shows the label of an item above it.
+#[derive(Component)]
+pub struct LabelText;
+
+pub fn display_labels(
+    labels: Query<(Entity, &ItemLabel, Option<&Children>), Changed<ItemLabel>>,
+    mut texts: Query<&mut Text2d, With<LabelText>>,
+    mut removed_labels: RemovedComponents<ItemLabel>,
+    children: Query<&Children>,
+    mut commands: Commands,
+) {
+    for (entity, label, children) in labels.iter() {
+        let existing = children
+            .into_iter()
+            .flat_map(|children| children.iter())
+            .find(|child| texts.contains(*child));
+
+        if let Some(child) = existing
+            && let Ok(mut text) = texts.get_mut(child)
+        {
+            text.0 = label.0.clone();
+            continue;
+        }
+
+        commands.entity(entity).with_children(|parent| {
+            parent.spawn((
+                LabelText,
+                Text2d::new(label.0.clone()),
+                TextFont::from_font_size(9.0),
+                Transform::from_xyz(0.0, 24.0, 0.1),
+            ));
+        });
+    }
+
+    for entity in removed_labels.read() {
+        let Ok(children) = children.get(entity) else {
+            continue;
+        };
+        for child in children.iter().filter(|child| texts.contains(*child)) {
+            commands.entity(child).despawn();
+        }
+    }
+}
+
+/// Shows the key a button is bound to.
+#[derive(Component)]
+pub struct ButtonKeyLabel;
+
+pub fn update_button_keys(
+    buttons: Query<(&LogicButton, &Children), Changed<LogicButton>>,
+    mut labels: Query<&mut Text2d, With<ButtonKeyLabel>>,
+) {
+    for (button, children) in buttons.iter() {
+        for child in children.iter() {
+            if let Ok(mut text) = labels.get_mut(child) {
+                text.0 = button
+                    .key
+                    .map(|key| format!("{key:?}").trim_start_matches("Digit").to_string())
+                    .unwrap_or_default();
+            }
+        }
+    }
+}
+
+pub fn display_junctions(
+    new_junctions: Query<Entity, Added<Junction>>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for entity in new_junctions.iter() {
         commands.entity(entity).insert((
-            Mesh2d(meshes.add(Mesh::from(Circle {
-                radius: 10.0,
-                ..default()
-            }))),
-            MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(1.0, 0., 0.)))),
+            Mesh2d(meshes.add(Mesh::from(Circle { radius: 5.0 }))),
+            MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(0.5, 0.5, 0.5)))),
         ));
     }
 }
+
+pub fn display_tunnels(
+    new_tunnels: Query<(Entity, &Tunnel), Added<Tunnel>>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (entity, tunnel) in new_tunnels.iter() {
+        commands
+            .entity(entity)
+            .insert((
+                Mesh2d(meshes.add(create_tunnel_mesh(40.0, 20.0))),
+                MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(0.5, 0.5, 0.5)))),
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text2d::new(tunnel.name.clone()),
+                    TextFont::from_font_size(10.0),
+                    TextColor(Color::BLACK),
+                    Transform::from_xyz(-4.0, 0.0, 0.1),
+                ));
+            });
+    }
+}
+
+pub fn display_plexers(
+    new_plexers: Query<(Entity, &Plexer), Added<Plexer>>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (entity, plexer) in new_plexers.iter() {
+        let (left_height, right_height) = plexer.side_heights();
+        let label = match plexer {
+            Plexer::Mux { .. } => "MUX",
+            Plexer::Demux { .. } => "DMX",
+            Plexer::Encoder { .. } => "ENC",
+            Plexer::Decoder { .. } => "DEC",
+        };
+
+        commands
+            .entity(entity)
+            .insert((
+                Mesh2d(meshes.add(create_trapezoid_mesh(
+                    plexer.size().x,
+                    left_height,
+                    right_height,
+                ))),
+                MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(0.5, 0.5, 0.5)))),
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text2d::new(label),
+                    TextFont::from_font_size(7.0),
+                    TextColor(Color::BLACK),
+                    Transform::from_xyz(0.0, 0.0, 0.1),
+                ));
+            });
+    }
+}
@@ -1,6 +1,13 @@
 use bevy::prelude::*;
 
-use crate::{link::Link, logic::Item};
+use crate::{
+    link::{InputAnchors, Link},
+    logic::{Item, Value},
+    renderer::gate::generate_mesh,
+};
+
+/// Thickness of the wires carrying a bus rather than a single bit.
+const BUS_THICKNESS: f32 = 3.0;
 
 pub struct RendererLinkPlugin;
 impl Plugin for RendererLinkPlugin {
@@ -9,10 +16,14 @@ impl Plugin for RendererLinkPlugin {
     }
 }
 
+/// Shows the width of the bus carried by a link.
+#[derive(Component)]
+struct LinkLabel;
+
 fn setup_links(
     query: Query<(Entity, &Link), Added<Link>>,
     mut commands: Commands,
-    item_query: Query<(&Transform, Entity)>,
+    item_query: Query<(&GlobalTransform, Entity)>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
@@ -24,30 +35,45 @@ fn setup_links(
             continue;
         };
 
-        commands.entity(entity).insert((
-            MeshMaterial2d(materials.add(ColorMaterial::from(Color::WHITE))),
-            Mesh2d(meshes.add(Mesh::from(Segment2d::new(
-                from_transform.translation.truncate(),
-                to_transform.translation.truncate(),
-            )))),
-            Transform::from_xyz(0.0, 0.0, -2.),
-            Link {
-                from: link.from,
-                to: link.to,
-                from_position: from_transform.translation.truncate(),
-                to_position: to_transform.translation.truncate(),
-            },
-        ));
+        commands
+            .entity(entity)
+            .insert((
+                MeshMaterial2d(materials.add(ColorMaterial::from(Color::WHITE))),
+                Mesh2d(meshes.add(Mesh::from(Segment2d::new(
+                    from_transform.translation().truncate(),
+                    to_transform.translation().truncate(),
+                )))),
+                Transform::from_xyz(0.0, 0.0, -2.),
+                Link {
+                    from: link.from,
+                    to: link.to,
+                    input: link.input,
+                    from_position: from_transform.translation().truncate(),
+                    to_position: to_transform.translation().truncate(),
+                },
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    LinkLabel,
+                    Text2d::new(""),
+                    TextFont::from_font_size(8.0),
+                    Transform::from_xyz(0.0, 0.0, 3.0),
+                ));
+            });
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn update_links(
-    mut links: Query<(&mut Mesh2d, &mut Link)>,
-    changed_item_query: Query<(&Transform, Entity), (With<Item>, Changed<Transform>)>,
-    all_item_query: Query<(&Transform, Entity), With<Item>>,
+    mut links: Query<(&mut Mesh2d, &mut Link, Option<&Children>)>,
+    changed_item_query: Query<(&GlobalTransform, Entity), (With<Item>, Changed<GlobalTransform>)>,
+    all_item_query: Query<(&GlobalTransform, Entity), With<Item>>,
+    values: Query<&Value>,
+    anchors: Query<&InputAnchors>,
+    mut labels: Query<(&mut Text2d, &mut Transform), With<LinkLabel>>,
     mut meshes: ResMut<Assets<Mesh>>,
 ) {
-    for (mut mesh, mut link) in links.iter_mut() {
+    for (mut mesh, mut link, children) in links.iter_mut() {
         let from_transform = match changed_item_query.get(link.from) {
             Ok(v) => v.0,
             Err(_) => match all_item_query.get(link.from) {
@@ -64,12 +90,45 @@ fn update_links(
             },
         };
 
-        mesh.0 = meshes.add(Mesh::from(Segment2d::new(
-            from_transform.translation.truncate(),
-            to_transform.translation.truncate(),
-        )));
+        let anchor = anchors
+            .get(link.from)
+            .ok()
+            .and_then(|anchors| anchors.0.get(link.input))
+            .copied()
+            .unwrap_or(Vec2::ZERO);
+        let from = from_transform
+            .transform_point(anchor.extend(0.0))
+            .truncate();
+        let to = to_transform.translation().truncate();
+        let width = values.get(link.to).map_or(1, |value| value.state.width());
+
+        mesh.0 = if width > 1 {
+            meshes.add(thick_segment(from, to, BUS_THICKNESS))
+        } else {
+            meshes.add(Mesh::from(Segment2d::new(from, to)))
+        };
 
-        link.from_position = from_transform.translation.truncate();
-        link.to_position = to_transform.translation.truncate();
+        for child in children.into_iter().flat_map(|children| children.iter()) {
+            if let Ok((mut text, mut transform)) = labels.get_mut(child) {
+                text.0 = if width > 1 {
+                    width.to_string()
+                } else {
+                    String::new()
+                };
+                transform.translation = ((from + to) / 2.0 + Vec2::new(0.0, 6.0)).extend(3.0);
+            }
+        }
+
+        link.from_position = from;
+        link.to_position = to;
     }
 }
+
+fn thick_segment(from: Vec2, to: Vec2, thickness: f32) -> Mesh {
+    let normal = (to - from).normalize_or_zero().perp() * thickness / 2.0;
+    let positions = [from + normal, from - normal, to - normal, to + normal]
+        .map(|p| [p.x, p.y, 0.0])
+        .to_vec();
+
+    generate_mesh(positions, vec![0, 1, 2, 0, 2, 3])
+}
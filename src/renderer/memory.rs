@@ -0,0 +1,154 @@
+use bevy::prelude::*;
+
+use crate::{
+    console::{Console, ConsoleAppExt, ConsoleCommand},
+    logic::memory::Memory,
+    renderer::spawn_pin_labels,
+    selection::Selected,
+};
+
+/// Words per row of the hex view.
+const ROW_WORDS: usize = 8;
+/// Rows shown at once by the hex view.
+const VIEW_ROWS: usize = 16;
+
+pub struct MemoryRendererPlugin;
+impl Plugin for MemoryRendererPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HexView>()
+            .add_console_command(
+                "memview",
+                "memview <address>, scroll the hex view of the selected memory to an address",
+            )
+            .add_systems(Startup, setup_hex_view)
+            .add_systems(
+                Update,
+                (display_memories, scroll_hex_view, display_hex_view),
+            );
+    }
+}
+
+/// First address shown by the hex view.
+#[derive(Resource, Default)]
+struct HexView {
+    first_address: usize,
+}
+
+#[derive(Component)]
+struct HexViewText;
+
+fn display_memories(
+    new_memories: Query<(Entity, &Memory), Added<Memory>>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (entity, memory) in new_memories.iter() {
+        let size = memory.size();
+        let mut entity = commands.entity(entity);
+        entity
+            .insert((
+                Mesh2d(meshes.add(Mesh::from(Rectangle::from_size(size)))),
+                MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(0.5, 0.5, 0.5)))),
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text2d::new(format!(
+                        "{}\n{}x{}",
+                        memory.name(),
+                        memory.contents.len(),
+                        memory.data_width
+                    )),
+                    TextFont::from_font_size(8.0),
+                    TextColor(Color::BLACK),
+                    TextLayout::new_with_justify(Justify::Center),
+                    Transform::from_xyz(0.0, 0.0, 0.1),
+                ));
+            });
+        spawn_pin_labels(&mut entity, size, memory.input_names(), &["Q"]);
+    }
+}
+
+fn setup_hex_view(mut commands: Commands) {
+    commands.spawn((
+        HexViewText,
+        Text::new(""),
+        TextFont::from_font_size(14.0),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(10.0),
+            top: Val::Px(10.0),
+            ..default()
+        },
+    ));
+}
+
+fn scroll_hex_view(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    mut view: ResMut<HexView>,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        if command.name != "memview" {
+            continue;
+        }
+
+        let address = command.args().first().map(|address| {
+            usize::from_str_radix(address.trim_start_matches("0x"), 16)
+                .map_err(|_| format!("invalid hexadecimal address `{address}`"))
+        });
+        match address {
+            Some(Ok(address)) => view.first_address = address - address % ROW_WORDS,
+            Some(Err(error)) => console.print(error),
+            None => console.print("usage: memview <address>"),
+        }
+    }
+}
+
+/// Dumps the contents of the selected memory, when exactly one is selected.
+fn display_hex_view(
+    memories: Query<Ref<Memory>, With<Selected>>,
+    view: Res<HexView>,
+    mut text: Single<&mut Text, With<HexViewText>>,
+) {
+    let mut selected = memories.iter();
+    let (Some(memory), None) = (selected.next(), selected.next()) else {
+        if !text.0.is_empty() {
+            text.0.clear();
+        }
+        return;
+    };
+    if !memory.is_changed() && !view.is_changed() && !text.0.is_empty() {
+        return;
+    }
+
+    let digits = memory.data_width.div_ceil(4) as usize;
+    let address_digits = memory.address_width.div_ceil(4).max(1) as usize;
+    let first = view
+        .first_address
+        .min(memory.contents.len().saturating_sub(1));
+    let first = first - first % ROW_WORDS;
+
+    let mut lines = vec![format!(
+        "{} {}x{}",
+        memory.name(),
+        memory.contents.len(),
+        memory.data_width
+    )];
+    for (row, words) in memory.contents[first..]
+        .chunks(ROW_WORDS)
+        .take(VIEW_ROWS)
+        .enumerate()
+    {
+        let words = words
+            .iter()
+            .map(|word| format!("{word:0digits$x}"))
+            .collect::<Vec<_>>();
+        lines.push(format!(
+            "{:0address_digits$x}: {}",
+            first + row * ROW_WORDS,
+            words.join(" ")
+        ));
+    }
+    text.0 = lines.join("\n");
+}
@@ -1,17 +1,78 @@
-use crate::renderer::{
-    gate::GateRendererPlugin, link::RendererLinkPlugin, shadow::ShadowRendererPlugin,
+use crate::{
+    logic::{input_pin_position, output_pin_position},
+    renderer::{
+        arithmetic::ArithmeticRendererPlugin, bus::BusRendererPlugin,
+        display::DisplayRendererPlugin, export::ExportRendererPlugin, gate::GateRendererPlugin,
+        link::RendererLinkPlugin, memory::MemoryRendererPlugin, probe::ProbeRendererPlugin,
+        sequential::SequentialRendererPlugin, shadow::ShadowRendererPlugin,
+        source::SourceRendererPlugin, truth_table::TruthTableRendererPlugin,
+    },
 };
-use bevy::prelude::*;
+use bevy::{prelude::*, sprite::Anchor};
 
-mod gate;
+mod arithmetic;
+mod bus;
+mod display;
+mod export;
+pub mod gate;
 mod link;
+mod memory;
+mod probe;
+mod sequential;
 pub mod shadow;
+mod source;
+mod truth_table;
 
 pub struct RendererPlugin;
 impl Plugin for RendererPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(GateRendererPlugin)
+            .add_plugins(BusRendererPlugin)
+            .add_plugins(ArithmeticRendererPlugin)
+            .add_plugins(MemoryRendererPlugin)
+            .add_plugins(SequentialRendererPlugin)
+            .add_plugins(DisplayRendererPlugin)
+            .add_plugins(SourceRendererPlugin)
+            .add_plugins(TruthTableRendererPlugin)
             .add_plugins(ShadowRendererPlugin)
-            .add_plugins(RendererLinkPlugin);
+            .add_plugins(RendererLinkPlugin)
+            .add_plugins(ExportRendererPlugin)
+            .add_plugins(ProbeRendererPlugin);
     }
 }
+
+/// Writes the name of each input and output pin inside the edges of a
+/// component body of the given size.
+pub(crate) fn spawn_pin_labels(
+    entity: &mut EntityCommands,
+    size: Vec2,
+    inputs: &[&str],
+    outputs: &[&str],
+) {
+    let margin = Vec2::new(2.0, 0.0);
+
+    entity.with_children(|parent| {
+        for (index, name) in inputs.iter().enumerate() {
+            let position = input_pin_position(size, index, inputs.len()) + margin;
+            parent.spawn((
+                Text2d::new(*name),
+                TextFont::from_font_size(6.0),
+                TextColor(Color::BLACK),
+                Anchor::CENTER_LEFT,
+                Transform::from_translation(position.extend(0.1)),
+            ));
+        }
+
+        for (index, name) in outputs.iter().enumerate() {
+            let y = output_pin_position(size, index, outputs.len()).y;
+            let position = Vec2::new(size.x / 2.0, y) - margin;
+            parent.spawn((
+                Text2d::new(*name),
+                TextFont::from_font_size(6.0),
+                TextColor(Color::BLACK),
+                Anchor::CENTER_RIGHT,
+                Transform::from_translation(position.extend(0.1)),
+            ));
+        }
+    });
+}
@@ -0,0 +1,88 @@
+use bevy::{prelude::*, sprite::Anchor};
+
+use crate::{debug::watch::Watched, renderer::gate::signal_color};
+
+/// Radius of the dot marking a watched item.
+const PROBE_RADIUS: f32 = 3.0;
+
+pub struct ProbeRendererPlugin;
+impl Plugin for ProbeRendererPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (display_probes, update_probes, remove_probes));
+    }
+}
+
+/// A dot under a watched item, colored after its value.
+#[derive(Component)]
+struct Probe;
+
+/// The value written next to a probe.
+#[derive(Component)]
+struct ProbeText;
+
+fn display_probes(
+    watched: Query<(Entity, &Watched), Added<Watched>>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (entity, watched) in watched.iter() {
+        let color = signal_color(&watched.value);
+        commands.entity(entity).with_children(|parent| {
+            parent
+                .spawn((
+                    Probe,
+                    Mesh2d(meshes.add(Circle::new(PROBE_RADIUS))),
+                    MeshMaterial2d(materials.add(ColorMaterial::from(color))),
+                    Transform::from_xyz(0.0, -20.0, 0.3),
+                ))
+                .with_child((
+                    ProbeText,
+                    Text2d::new(watched.value.to_string()),
+                    TextFont::from_font_size(8.0),
+                    TextColor(color),
+                    Anchor::CENTER_LEFT,
+                    Transform::from_xyz(PROBE_RADIUS + 2.0, 0.0, 0.0),
+                ));
+        });
+    }
+}
+
+fn update_probes(
+    watched: Query<(&Watched, &Children), Changed<Watched>>,
+    probes: Query<(&MeshMaterial2d<ColorMaterial>, &Children), With<Probe>>,
+    mut texts: Query<(&mut Text2d, &mut TextColor), With<ProbeText>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (watched, children) in watched.iter() {
+        let color = signal_color(&watched.value);
+        for (material, probe_children) in children.iter().filter_map(|child| probes.get(child).ok())
+        {
+            if let Some(material) = materials.get_mut(&material.0) {
+                material.color = color;
+            }
+            for child in probe_children.iter() {
+                if let Ok((mut text, mut text_color)) = texts.get_mut(child) {
+                    text.0 = watched.value.to_string();
+                    text_color.0 = color;
+                }
+            }
+        }
+    }
+}
+
+fn remove_probes(
+    mut removed: RemovedComponents<Watched>,
+    children: Query<&Children>,
+    probes: Query<(), With<Probe>>,
+    mut commands: Commands,
+) {
+    for entity in removed.read() {
+        let Ok(children) = children.get(entity) else {
+            continue;
+        };
+        for child in children.iter().filter(|child| probes.contains(*child)) {
+            commands.entity(child).despawn();
+        }
+    }
+}
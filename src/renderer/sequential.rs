@@ -0,0 +1,108 @@
+use bevy::prelude::*;
+
+use crate::{
+    logic::{
+        clock::{CLOCK_SIZE, Clock},
+        sequential::Register,
+    },
+    renderer::spawn_pin_labels,
+    signal::Signal,
+};
+
+pub struct SequentialRendererPlugin;
+impl Plugin for SequentialRendererPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (display_clocks, display_registers, update_register_values),
+        );
+    }
+}
+
+/// The current value written on a register body.
+#[derive(Component)]
+struct RegisterValue;
+
+/// Hexadecimal digits of a signal, `x` standing for an unknown digit.
+fn hex_digits(signal: &Signal) -> String {
+    let digits = signal.width().div_ceil(4);
+    match signal.value() {
+        Some(value) => format!("{value:0width$x}", width = digits as usize),
+        None => "x".repeat(digits as usize),
+    }
+}
+
+fn display_clocks(
+    new_clocks: Query<Entity, Added<Clock>>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for entity in new_clocks.iter() {
+        commands
+            .entity(entity)
+            .insert((
+                Mesh2d(meshes.add(Mesh::from(Rectangle::from_length(CLOCK_SIZE)))),
+                MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(1.0, 0.0, 0.0)))),
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text2d::new("CLK"),
+                    TextFont::from_font_size(7.0),
+                    TextColor(Color::BLACK),
+                    Transform::from_xyz(0.0, 0.0, 0.1),
+                ));
+            });
+    }
+}
+
+fn display_registers(
+    new_registers: Query<(Entity, &Register), Added<Register>>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (entity, register) in new_registers.iter() {
+        let size = register.size();
+        let mut entity = commands.entity(entity);
+        entity
+            .insert((
+                Mesh2d(meshes.add(Mesh::from(Rectangle::from_size(size)))),
+                MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(0.5, 0.5, 0.5)))),
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text2d::new(register.name()),
+                    TextFont::from_font_size(7.0),
+                    TextColor(Color::BLACK),
+                    Transform::from_xyz(0.0, size.y / 2.0 - 5.0, 0.1),
+                ));
+                parent.spawn((
+                    RegisterValue,
+                    Text2d::new(hex_digits(&register.value)),
+                    TextFont::from_font_size(8.0),
+                    TextColor(Color::BLACK),
+                    Transform::from_xyz(0.0, 0.0, 0.1),
+                ));
+            });
+        spawn_pin_labels(
+            &mut entity,
+            size,
+            register.input_names(),
+            register.output_names(),
+        );
+    }
+}
+
+fn update_register_values(
+    registers: Query<(&Register, &Children), Changed<Register>>,
+    mut texts: Query<&mut Text2d, With<RegisterValue>>,
+) {
+    for (register, children) in registers.iter() {
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(child) {
+                text.0 = hex_digits(&register.value);
+            }
+        }
+    }
+}
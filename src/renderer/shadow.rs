@@ -17,6 +17,7 @@ impl Plugin for ShadowRendererPlugin {
 #[derive(Component)]
 pub struct ShadowEntity;
 
+#[allow(clippy::type_complexity)]
 pub fn display_shadows(
     mut commands: Commands,
     mut added_selection: Query<
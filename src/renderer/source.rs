@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+
+use crate::logic::source::{Constant, Pull};
+
+pub struct SourceRendererPlugin;
+impl Plugin for SourceRendererPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (display_constants, display_pulls));
+    }
+}
+
+fn display_constants(
+    new_constants: Query<(Entity, &Constant), Added<Constant>>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (entity, constant) in new_constants.iter() {
+        commands
+            .entity(entity)
+            .insert((
+                Mesh2d(meshes.add(Mesh::from(Rectangle::from_size(constant.size())))),
+                MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(0.5, 0.5, 0.5)))),
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text2d::new(constant.label()),
+                    TextFont::from_font_size(9.0),
+                    TextColor(Color::BLACK),
+                    Transform::from_xyz(0.0, 0.0, 0.1),
+                ));
+            });
+    }
+}
+
+fn display_pulls(
+    new_pulls: Query<(Entity, &Pull), Added<Pull>>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (entity, pull) in new_pulls.iter() {
+        let label = match pull {
+            Pull::Up => "PU",
+            Pull::Down => "PD",
+        };
+        commands
+            .entity(entity)
+            .insert((
+                Mesh2d(meshes.add(Mesh::from(Rectangle::from_size(Pull::SIZE)))),
+                MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(0.8, 0.7, 0.4)))),
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text2d::new(label),
+                    TextFont::from_font_size(8.0),
+                    TextColor(Color::BLACK),
+                    Transform::from_xyz(0.0, 0.0, 0.1),
+                ));
+            });
+    }
+}
@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+
+use crate::{analysis::truth_table::TruthTable, signal::Signal};
+
+/// Rows shown by the panel, the whole table being in the CSV export.
+const PANEL_ROWS: usize = 32;
+
+pub struct TruthTableRendererPlugin;
+impl Plugin for TruthTableRendererPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_truth_table_panel)
+            .add_systems(Update, display_truth_table);
+    }
+}
+
+#[derive(Component)]
+struct TruthTableText;
+
+fn setup_truth_table_panel(mut commands: Commands) {
+    commands.spawn((
+        TruthTableText,
+        Text::new(""),
+        TextFont::from_font_size(14.0),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(10.0),
+            top: Val::Px(10.0),
+            ..default()
+        },
+    ));
+}
+
+/// Writes each cell right-aligned under the name of its column.
+fn cells(names: &[String], cells: &[Signal]) -> String {
+    names
+        .iter()
+        .zip(cells)
+        .map(|(name, cell)| format!("{:>width$}", cell.to_string(), width = name.len()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn display_truth_table(table: Res<TruthTable>, mut text: Single<&mut Text, With<TruthTableText>>) {
+    if !table.is_changed() {
+        return;
+    }
+    if !table.visible {
+        text.0.clear();
+        return;
+    }
+
+    let header = format!("{} | {}", table.inputs.join(" "), table.outputs.join(" "));
+    let mut lines = vec![header];
+    for row in table.rows.iter().take(PANEL_ROWS) {
+        let (inputs, outputs) = row.split_at(table.inputs.len());
+        lines.push(format!(
+            "{} | {}",
+            cells(&table.inputs, inputs),
+            cells(&table.outputs, outputs)
+        ));
+    }
+    if table.rows.len() > PANEL_ROWS {
+        lines.push(format!(
+            "... {} more rows, see `ttcsv`",
+            table.rows.len() - PANEL_ROWS
+        ));
+    }
+    text.0 = lines.join("\n");
+}
@@ -0,0 +1,11 @@
+use crate::{
+    logic::arithmetic::Arithmetic,
+    selection::{CustomCollider, in_body},
+};
+use bevy::prelude::*;
+
+impl CustomCollider for Arithmetic {
+    fn contains_point(&self, local_point: Vec2) -> bool {
+        in_body(self.size(), local_point)
+    }
+}
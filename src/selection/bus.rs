@@ -0,0 +1,27 @@
+use crate::{
+    logic::{
+        OutputPin, PIN_RADIUS,
+        bus::{Merger, Splitter},
+    },
+    selection::{CustomCollider, in_body},
+};
+use bevy::prelude::*;
+
+impl CustomCollider for Splitter {
+    fn contains_point(&self, local_point: Vec2) -> bool {
+        in_body(self.size(), local_point)
+    }
+}
+
+impl CustomCollider for Merger {
+    fn contains_point(&self, local_point: Vec2) -> bool {
+        in_body(self.size(), local_point)
+    }
+}
+
+impl CustomCollider for OutputPin {
+    fn contains_point(&self, local_point: Vec2) -> bool {
+        let radius = PIN_RADIUS + 1.0;
+        local_point.length_squared() <= radius * radius
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{logic::LogicButton, selection::CustomCollider};
+use crate::{
+    logic::{ButtonKind, KNOB_RADIUS, LogicButton},
+    selection::CustomCollider,
+};
 use bevy::prelude::*;
 
 impl CustomCollider for LogicButton {
@@ -10,6 +13,15 @@ impl CustomCollider for LogicButton {
 impl LogicButton {
     pub fn contains_point(&self, local_point: Vec2) -> bool {
         let radius = 10.0;
-        local_point.length_squared() <= radius * radius
+        match self.kind {
+            ButtonKind::Toggle => local_point.length_squared() <= radius * radius,
+            ButtonKind::Momentary => local_point.abs().max_element() <= radius,
+        }
+    }
+
+    /// Whether a click at `local_point` operates the button rather than
+    /// selecting it.
+    pub fn on_knob(&self, local_point: Vec2) -> bool {
+        local_point.length_squared() <= KNOB_RADIUS * KNOB_RADIUS
     }
 }
@@ -0,0 +1,11 @@
+use crate::{
+    logic::display::SegmentDisplay,
+    selection::{CustomCollider, in_body},
+};
+use bevy::prelude::*;
+
+impl CustomCollider for SegmentDisplay {
+    fn contains_point(&self, local_point: Vec2) -> bool {
+        in_body(self.size(), local_point)
+    }
+}
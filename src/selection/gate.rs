@@ -13,6 +13,7 @@ impl Gate {
             Gate::And(_, _) => self.check_and(local_point),
             Gate::Or(_, _) => self.check_or(local_point),
             Gate::Not(_) => self.check_not(local_point),
+            Gate::TriState(_, _) => self.check_tri_state(local_point),
         }
     }
 
@@ -51,6 +52,19 @@ impl Gate {
         p.y.abs() <= max_y_at_x
     }
 
+    fn check_tri_state(&self, p: Vec2) -> bool {
+        let width = 40.0;
+        let height = 30.0;
+        let left_x = -width / 2.0;
+
+        if p.x < left_x || p.x > width / 2.0 {
+            return false;
+        }
+
+        let t = (p.x - left_x) / width;
+        p.y.abs() <= (height / 2.0) * (1.0 - t)
+    }
+
     fn check_or(&self, p: Vec2) -> bool {
         let width = 50.0;
         let height = 40.0;
@@ -0,0 +1,15 @@
+use crate::{logic::Junction, selection::CustomCollider};
+use bevy::prelude::*;
+
+impl CustomCollider for Junction {
+    fn contains_point(&self, local_point: Vec2) -> bool {
+        self.contains_point(local_point)
+    }
+}
+
+impl Junction {
+    pub fn contains_point(&self, local_point: Vec2) -> bool {
+        let radius = 6.0;
+        local_point.length_squared() <= radius * radius
+    }
+}
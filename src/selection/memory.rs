@@ -0,0 +1,11 @@
+use crate::{
+    logic::memory::Memory,
+    selection::{CustomCollider, in_body},
+};
+use bevy::prelude::*;
+
+impl CustomCollider for Memory {
+    fn contains_point(&self, local_point: Vec2) -> bool {
+        in_body(self.size(), local_point)
+    }
+}
@@ -2,12 +2,31 @@ use crate::renderer::shadow::ShadowEffect;
 use crate::{
     cursor::CursorPosition,
     link::Link,
-    logic::{Gate, LogicButton},
+    logic::{
+        Gate, Junction, LogicButton, OutputPin, Tunnel,
+        arithmetic::Arithmetic,
+        bus::{Merger, Splitter},
+        clock::Clock,
+        display::SegmentDisplay,
+        memory::Memory,
+        plexer::Plexer,
+        sequential::Register,
+        source::{Constant, Pull},
+    },
 };
 use bevy::prelude::*;
+mod arithmetic;
+mod bus;
 mod button;
+mod display;
 mod gate;
+mod junction;
 mod link;
+mod memory;
+mod plexer;
+mod sequential;
+mod source;
+mod tunnel;
 
 #[derive(Component, Default)]
 #[require(ShadowEffect)]
@@ -20,17 +39,40 @@ impl Plugin for SelectionPlugin {
             Update,
             (
                 generic_click_system::<LogicButton>,
+                generic_click_system::<Clock>,
+                generic_click_system::<Constant>,
+                generic_click_system::<Pull>,
                 generic_click_system::<Gate>,
+                generic_click_system::<Junction>,
+                generic_click_system::<Tunnel>,
+                generic_click_system::<Splitter>,
+                generic_click_system::<Merger>,
+                generic_click_system::<Plexer>,
+                generic_click_system::<Arithmetic>,
+                generic_click_system::<Memory>,
+                generic_click_system::<Register>,
+                generic_click_system::<SegmentDisplay>,
+                generic_click_system::<OutputPin>,
                 generic_click_system::<Link>,
             )
-                .chain(),
+                .chain()
+                .in_set(SelectionSystems),
         );
     }
 }
 
+/// The systems selecting the items clicked on.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SelectionSystems;
+
 #[derive(Component)]
 pub struct Selected;
 
+/// Whether `p` lies in a rectangular component body of the given size.
+fn in_body(size: Vec2, p: Vec2) -> bool {
+    p.x.abs() <= size.x / 2.0 && p.y.abs() <= size.y / 2.0
+}
+
 pub trait CustomCollider: Component {
     fn contains_point(&self, local_point: Vec2) -> bool;
 }
@@ -0,0 +1,25 @@
+use crate::{logic::plexer::Plexer, selection::CustomCollider};
+use bevy::prelude::*;
+
+impl CustomCollider for Plexer {
+    fn contains_point(&self, local_point: Vec2) -> bool {
+        self.contains_point(local_point)
+    }
+}
+
+impl Plexer {
+    pub fn contains_point(&self, p: Vec2) -> bool {
+        let width = self.size().x;
+        let (left_height, right_height) = self.side_heights();
+        let left_x = -width / 2.0;
+
+        if p.x < left_x || p.x > width / 2.0 {
+            return false;
+        }
+
+        let t = (p.x - left_x) / width;
+        let half_height = (left_height + (right_height - left_height) * t) / 2.0;
+
+        p.y.abs() <= half_height
+    }
+}
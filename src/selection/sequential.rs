@@ -0,0 +1,20 @@
+use crate::{
+    logic::{
+        clock::{CLOCK_SIZE, Clock},
+        sequential::Register,
+    },
+    selection::{CustomCollider, in_body},
+};
+use bevy::prelude::*;
+
+impl CustomCollider for Clock {
+    fn contains_point(&self, local_point: Vec2) -> bool {
+        in_body(Vec2::splat(CLOCK_SIZE), local_point)
+    }
+}
+
+impl CustomCollider for Register {
+    fn contains_point(&self, local_point: Vec2) -> bool {
+        in_body(self.size(), local_point)
+    }
+}
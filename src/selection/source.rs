@@ -0,0 +1,17 @@
+use crate::{
+    logic::source::{Constant, Pull},
+    selection::{CustomCollider, in_body},
+};
+use bevy::prelude::*;
+
+impl CustomCollider for Constant {
+    fn contains_point(&self, local_point: Vec2) -> bool {
+        in_body(self.size(), local_point)
+    }
+}
+
+impl CustomCollider for Pull {
+    fn contains_point(&self, local_point: Vec2) -> bool {
+        in_body(Pull::SIZE, local_point)
+    }
+}
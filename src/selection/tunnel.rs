@@ -0,0 +1,17 @@
+use crate::{logic::Tunnel, selection::CustomCollider};
+use bevy::prelude::*;
+
+impl CustomCollider for Tunnel {
+    fn contains_point(&self, local_point: Vec2) -> bool {
+        self.contains_point(local_point)
+    }
+}
+
+impl Tunnel {
+    pub fn contains_point(&self, local_point: Vec2) -> bool {
+        let width = 40.0;
+        let height = 20.0;
+
+        local_point.x.abs() <= width / 2.0 && local_point.y.abs() <= height / 2.0
+    }
+}
@@ -0,0 +1,228 @@
+use std::fs;
+
+use bevy::prelude::*;
+
+use crate::{
+    analysis::SelectedPorts,
+    console::{Console, ConsoleAppExt, ConsoleCommand},
+    logic::{ItemLabel, LogicSystems, SimulationTicks, Value},
+    signal::Signal,
+    waveform::panel::WaveformPanelPlugin,
+};
+
+pub mod panel;
+pub mod vcd;
+
+/// Records the values of chosen items over the simulation ticks, to study
+/// how a circuit behaves in time.
+pub struct WaveformPlugin;
+impl Plugin for WaveformPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(WaveformPanelPlugin)
+            .init_resource::<Waveform>()
+            .add_console_command(
+                "record",
+                "record [label ...|stop], record the selected or labelled items, or stop recording",
+            )
+            .add_console_command(
+                "vcd",
+                "vcd <path>, write the recording as a value change dump",
+            )
+            .add_systems(Update, waveform_commands)
+            .add_systems(Update, record_values.after(LogicSystems));
+    }
+}
+
+/// The values taken by one recorded item.
+pub struct Trace {
+    pub entity: Entity,
+    pub name: String,
+    /// The ticks at which the value changed, with the new value, the first
+    /// one being the value when the recording started. A value set while the
+    /// simulation is paused replaces the one of the same tick.
+    pub changes: Vec<(u64, Signal)>,
+}
+
+impl Trace {
+    /// The value of the item at `tick`, if it was recorded by then.
+    pub fn value_at(&self, tick: u64) -> Option<Signal> {
+        let index = self.changes.partition_point(|(change, _)| *change <= tick);
+        Some(self.changes.get(index.checked_sub(1)?)?.1)
+    }
+
+    /// The widest value the item took.
+    pub fn width(&self) -> u8 {
+        self.changes
+            .iter()
+            .map(|(_, signal)| signal.width())
+            .max()
+            .unwrap_or(1)
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Waveform {
+    pub traces: Vec<Trace>,
+    /// The tick the recording started at.
+    pub start: u64,
+    /// The last tick recorded.
+    pub end: u64,
+    pub recording: bool,
+}
+
+impl Waveform {
+    /// Records the values of the items at `tick`, `value` giving that of an
+    /// item. Items deleted during the recording keep their last value.
+    fn sample(&mut self, tick: u64, value: impl Fn(Entity) -> Option<Signal>) {
+        self.end = tick;
+        for trace in self.traces.iter_mut() {
+            let Some(value) = value(trace.entity) else {
+                continue;
+            };
+            if trace.changes.last().is_some_and(|(last, _)| *last == tick) {
+                trace.changes.pop();
+            }
+            if trace.changes.last().map(|(_, state)| *state) != Some(value) {
+                trace.changes.push((tick, value));
+            }
+        }
+    }
+}
+
+fn waveform_commands(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    ports: SelectedPorts,
+    labels: Query<(Entity, &ItemLabel), With<Value>>,
+    values: Query<&Value>,
+    ticks: Res<SimulationTicks>,
+    mut waveform: ResMut<Waveform>,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        let args = command.args();
+        match (command.name.as_str(), args.as_slice()) {
+            ("record", ["stop"]) => {
+                waveform.recording = false;
+                console.print(format!(
+                    "recorded {} ticks",
+                    waveform.end.saturating_sub(waveform.start)
+                ));
+            }
+            ("record", []) => {
+                let (inputs, outputs) = ports.ports();
+                let items = inputs.into_iter().chain(outputs);
+                let items = items.map(|port| (port.entity, port.name)).collect();
+                start_recording(&mut waveform, items, &values, ticks.0, &mut console);
+            }
+            ("record", names) => {
+                let mut items = Vec::new();
+                for name in names {
+                    let mut labelled = labels.iter().filter(|(_, label)| label.0 == *name);
+                    match labelled.next() {
+                        Some((entity, _)) => items.push((entity, name.to_string())),
+                        None => console.print(format!("no item labelled {name}")),
+                    }
+                }
+                start_recording(&mut waveform, items, &values, ticks.0, &mut console);
+            }
+            ("vcd", [path]) => {
+                if waveform.traces.is_empty() {
+                    console.print("nothing recorded");
+                    continue;
+                }
+                match fs::write(path, vcd::write(&waveform)) {
+                    Ok(()) => console.print(format!("wrote {path}")),
+                    Err(error) => console.print(format!("cannot write {path}: {error}")),
+                }
+            }
+            ("vcd", _) => console.print("usage: vcd <path>"),
+            _ => {}
+        }
+    }
+}
+
+/// Restarts the recording with `items`, named after the second member, from
+/// their values at `tick`.
+fn start_recording(
+    waveform: &mut Waveform,
+    items: Vec<(Entity, String)>,
+    values: &Query<&Value>,
+    tick: u64,
+    console: &mut Console,
+) {
+    if items.is_empty() {
+        console.print("no item to record");
+        return;
+    }
+
+    console.print(format!("recording {} items", items.len()));
+    *waveform = Waveform {
+        traces: items
+            .into_iter()
+            .map(|(entity, name)| Trace {
+                entity,
+                name,
+                changes: Vec::new(),
+            })
+            .collect(),
+        start: tick,
+        end: tick,
+        recording: true,
+    };
+    waveform.sample(tick, |entity| {
+        values.get(entity).ok().map(|value| value.state)
+    });
+}
+
+/// Records the values after each tick, and the ones set by hand while the
+/// simulation is paused.
+fn record_values(
+    values: Query<&Value>,
+    ticks: Res<SimulationTicks>,
+    mut waveform: ResMut<Waveform>,
+) {
+    if !waveform.recording {
+        return;
+    }
+    waveform.sample(ticks.0, |entity| {
+        values.get(entity).ok().map(|value| value.state)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_keep_only_the_changes() {
+        let entity = Entity::from_raw_u32(1).unwrap();
+        let mut waveform = Waveform {
+            traces: vec![Trace {
+                entity,
+                name: "a".to_string(),
+                changes: Vec::new(),
+            }],
+            start: 4,
+            end: 4,
+            recording: true,
+        };
+        let bit = |level| move |_| Some(Signal::bit(level));
+        waveform.sample(4, bit(false));
+        waveform.sample(5, bit(false));
+        waveform.sample(6, bit(true));
+        waveform.sample(7, bit(true));
+        // Toggled twice by hand while paused at tick 7.
+        waveform.sample(7, bit(false));
+        waveform.sample(7, bit(true));
+        waveform.sample(8, |_| None);
+
+        let trace = &waveform.traces[0];
+        assert_eq!(
+            trace.changes,
+            [(4, Signal::bit(false)), (6, Signal::bit(true))]
+        );
+        assert_eq!(trace.value_at(3), None);
+        assert_eq!(trace.value_at(5), Some(Signal::bit(false)));
+        assert_eq!(waveform.end, 8);
+    }
+}
@@ -0,0 +1,400 @@
+use bevy::{
+    input::{InputSystems, mouse::AccumulatedMouseScroll},
+    prelude::*,
+    window::PrimaryWindow,
+};
+
+use crate::{
+    console::{Console, ConsoleAppExt, ConsoleCommand},
+    renderer::gate::signal_color,
+    selection::Selected,
+    waveform::{Trace, Waveform},
+};
+
+/// Distance from the top of the window, below the camera information.
+const PANEL_TOP: f32 = 70.0;
+const HEADER_HEIGHT: f32 = 20.0;
+const ROW_HEIGHT: f32 = 24.0;
+/// Width of the column holding the names of the traces.
+const NAME_WIDTH: f32 = 140.0;
+const FONT_SIZE: f32 = 12.0;
+/// Rough width of a character, to tell whether a bus value fits its box.
+const CHAR_WIDTH: f32 = 7.0;
+const DEFAULT_SCALE: f32 = 8.0;
+const MIN_SCALE: f32 = 0.05;
+const MAX_SCALE: f32 = 64.0;
+const ZOOM_SPEED: f32 = 1.25;
+/// Pixels the traces move by per step of the wheel.
+const SCROLL_STEP: f32 = 40.0;
+
+/// A timing diagram of the recorded items, along the top of the window.
+pub struct WaveformPanelPlugin;
+impl Plugin for WaveformPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WaveformView>()
+            .add_console_command("waves", "waves, show or hide the waveform panel")
+            .add_systems(Startup, setup_panel)
+            .add_systems(PreUpdate, (panel_scroll, panel_clicks).after(InputSystems))
+            .add_systems(Update, (panel_commands, display_panel).chain());
+    }
+}
+
+/// The part of the recording shown by the panel.
+#[derive(Resource)]
+pub struct WaveformView {
+    pub visible: bool,
+    /// Pixels per tick.
+    pub scale: f32,
+    /// The tick at the left edge, `None` to follow the last ticks recorded.
+    pub first: Option<u64>,
+    /// The two cursors measuring the distance between ticks.
+    pub cursors: [Option<u64>; 2],
+}
+
+impl Default for WaveformView {
+    fn default() -> Self {
+        WaveformView {
+            visible: false,
+            scale: DEFAULT_SCALE,
+            first: None,
+            cursors: [None; 2],
+        }
+    }
+}
+
+impl WaveformView {
+    /// The ticks shown in a track `width` pixels wide, last excluded.
+    fn ticks(&self, waveform: &Waveform, width: f32) -> (u64, u64) {
+        let count = (width / self.scale).ceil() as u64;
+        let first = self
+            .first
+            .unwrap_or((waveform.end + 1).saturating_sub(count))
+            .max(waveform.start);
+        (first, first + count)
+    }
+
+    /// The left edge of `tick` in the track, when the track starts at `first`.
+    fn x(&self, tick: u64, first: u64) -> f32 {
+        tick.saturating_sub(first) as f32 * self.scale
+    }
+}
+
+#[derive(Component)]
+struct WaveformPanel;
+
+fn setup_panel(mut commands: Commands) {
+    commands.spawn((
+        WaveformPanel,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(PANEL_TOP),
+            left: Val::Px(0.0),
+            width: Val::Percent(100.0),
+            overflow: Overflow::clip(),
+            display: Display::None,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.05, 0.05, 0.05, 0.9)),
+    ));
+}
+
+fn panel_commands(
+    mut console_commands: MessageReader<ConsoleCommand>,
+    waveform: Res<Waveform>,
+    mut view: ResMut<WaveformView>,
+    mut console: ResMut<Console>,
+) {
+    for command in console_commands.read() {
+        if command.name != "waves" {
+            continue;
+        }
+
+        view.visible = !view.visible;
+        if view.visible && waveform.traces.is_empty() {
+            console.print("nothing recorded, see `record`");
+        }
+    }
+}
+
+/// Where the mouse is in the panel: its position from the left of the
+/// tracks and the top of the panel, and the tick it points at.
+fn pointer(window: &Window, waveform: &Waveform, view: &WaveformView) -> Option<(Vec2, u64)> {
+    let height = HEADER_HEIGHT + waveform.traces.len() as f32 * ROW_HEIGHT;
+    let cursor = window.cursor_position()?;
+    let position = Vec2::new(cursor.x - NAME_WIDTH, cursor.y - PANEL_TOP);
+    if !view.visible || !(0.0..height).contains(&position.y) {
+        return None;
+    }
+
+    let (first, _) = view.ticks(waveform, window.width() - NAME_WIDTH);
+    Some((position, first + (position.x.max(0.0) / view.scale) as u64))
+}
+
+/// Zooms the traces with the wheel, or scrolls them while Shift is held,
+/// hiding the wheel from the canvas underneath.
+fn panel_scroll(
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut scroll: ResMut<AccumulatedMouseScroll>,
+    keys: Res<ButtonInput<KeyCode>>,
+    waveform: Res<Waveform>,
+    mut view: ResMut<WaveformView>,
+) {
+    let Some((position, tick)) = pointer(&window, &waveform, &view) else {
+        return;
+    };
+    if scroll.delta.y == 0.0 {
+        return;
+    }
+
+    let track_width = window.width() - NAME_WIDTH;
+    let forward = scroll.delta.y > 0.0;
+    if keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        let (first, _) = view.ticks(&waveform, track_width);
+        let step = (SCROLL_STEP / view.scale).ceil() as u64;
+        let first = match forward {
+            true => first.saturating_sub(step),
+            false => first + step,
+        };
+        // Scrolling past the end follows the recording again.
+        let count = (track_width / view.scale) as u64;
+        view.first = (first + count <= waveform.end).then_some(first);
+    } else {
+        let scale = match forward {
+            true => view.scale * ZOOM_SPEED,
+            false => view.scale / ZOOM_SPEED,
+        };
+        view.scale = scale.clamp(MIN_SCALE, MAX_SCALE);
+        // Keeps the tick under the mouse in place.
+        if view.first.is_some() {
+            let offset = (position.x.max(0.0) / view.scale) as u64;
+            view.first = Some(tick.saturating_sub(offset));
+        }
+    }
+    scroll.delta = Vec2::ZERO;
+}
+
+/// Places cursor A with a left click and cursor B with a right click, the
+/// left click also selecting the item of the trace, and hides these clicks
+/// from the canvas underneath.
+fn panel_clicks(
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut mouse_buttons: ResMut<ButtonInput<MouseButton>>,
+    waveform: Res<Waveform>,
+    mut view: ResMut<WaveformView>,
+    selected: Query<Entity, With<Selected>>,
+    mut commands: Commands,
+) {
+    let Some((position, tick)) = pointer(&window, &waveform, &view) else {
+        return;
+    };
+
+    if mouse_buttons.clear_just_pressed(MouseButton::Left) {
+        let row = ((position.y - HEADER_HEIGHT) / ROW_HEIGHT).floor();
+        if row >= 0.0
+            && let Some(trace) = waveform.traces.get(row as usize)
+        {
+            for entity in selected.iter() {
+                commands.entity(entity).remove::<Selected>();
+            }
+            if let Ok(mut item) = commands.get_entity(trace.entity) {
+                item.insert(Selected);
+            }
+        }
+        if position.x >= 0.0 {
+            view.cursors[0] = Some(tick);
+        }
+    }
+
+    if mouse_buttons.clear_just_pressed(MouseButton::Right) && position.x >= 0.0 {
+        view.cursors[1] = Some(tick);
+    }
+}
+
+/// A rectangle at the given place of the panel.
+fn rectangle(left: f32, top: f32, width: f32, height: f32) -> Node {
+    Node {
+        position_type: PositionType::Absolute,
+        left: Val::Px(left),
+        top: Val::Px(top),
+        width: Val::Px(width),
+        height: Val::Px(height),
+        ..default()
+    }
+}
+
+fn label(text: String, left: f32, top: f32, color: Color) -> impl Bundle {
+    (
+        Text::new(text),
+        TextFont {
+            font_size: FONT_SIZE,
+            ..default()
+        },
+        TextColor(color),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(left),
+            top: Val::Px(top),
+            ..default()
+        },
+    )
+}
+
+/// What the panel last drew of the recording.
+#[derive(Default, PartialEq)]
+struct Drawn {
+    start: u64,
+    ticks: (u64, u64),
+    /// The last tick recorded, if in view.
+    end: u64,
+    /// The number of values of each trace.
+    changes: Vec<usize>,
+}
+
+fn display_panel(
+    panel: Single<(Entity, &mut Node), With<WaveformPanel>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    waveform: Res<Waveform>,
+    view: Res<WaveformView>,
+    mut width: Local<f32>,
+    mut drawn: Local<Drawn>,
+    mut commands: Commands,
+) {
+    let resized = *width != window.width();
+    let track_width = window.width() - NAME_WIDTH;
+    let (first, last) = view.ticks(&waveform, track_width);
+    // The recording grows every tick, but the panel only changes when this
+    // moves the ticks in view or adds values to draw.
+    let shown = Drawn {
+        start: waveform.start,
+        ticks: (first, last),
+        end: waveform.end.min(last),
+        changes: waveform
+            .traces
+            .iter()
+            .map(|trace| trace.changes.len())
+            .collect(),
+    };
+    if *drawn == shown && !view.is_changed() && !resized {
+        return;
+    }
+    *width = window.width();
+    *drawn = shown;
+
+    let (entity, mut node) = panel.into_inner();
+    node.display = match view.visible {
+        true => Display::Flex,
+        false => Display::None,
+    };
+    let height = HEADER_HEIGHT + waveform.traces.len() as f32 * ROW_HEIGHT;
+    node.height = Val::Px(height);
+
+    let mut panel = commands.entity(entity);
+    panel.despawn_related::<Children>();
+    if !view.visible {
+        return;
+    }
+
+    let relative = |tick: u64| tick.saturating_sub(waveform.start);
+    let mut header = format!(
+        "ticks {}-{}, {:.2} px/tick",
+        relative(first),
+        relative(last),
+        view.scale
+    );
+    if let [Some(a), b] = view.cursors {
+        header.push_str(&format!(", A {}", relative(a)));
+        if let Some(b) = b {
+            header.push_str(&format!(", B {}, B-A {}", relative(b), b as i64 - a as i64));
+        }
+    }
+
+    panel.with_children(|parent| {
+        parent.spawn(label(header, 4.0, 3.0, Color::WHITE));
+
+        for (row, trace) in waveform.traces.iter().enumerate() {
+            let top = HEADER_HEIGHT + row as f32 * ROW_HEIGHT;
+            let shown = view.cursors[0].unwrap_or(waveform.end);
+            let name = match trace.value_at(shown) {
+                Some(value) => format!("{} {value}", trace.name),
+                None => trace.name.clone(),
+            };
+            parent.spawn(label(name, 4.0, top + 5.0, Color::WHITE));
+            parent.spawn((
+                rectangle(0.0, top + ROW_HEIGHT - 1.0, NAME_WIDTH + track_width, 1.0),
+                BackgroundColor(Color::srgb(0.2, 0.2, 0.2)),
+            ));
+            spawn_trace(parent, trace, &view, &waveform, (first, last), top);
+        }
+
+        let colors = [Color::srgb(1.0, 1.0, 0.0), Color::srgb(0.0, 1.0, 1.0)];
+        for (cursor, color) in view.cursors.iter().zip(colors) {
+            if let Some(tick) = cursor
+                && (first..last).contains(tick)
+            {
+                let x = NAME_WIDTH + view.x(*tick, first);
+                parent.spawn((
+                    rectangle(x, HEADER_HEIGHT, 1.0, height - HEADER_HEIGHT),
+                    BackgroundColor(color),
+                ));
+            }
+        }
+    });
+}
+
+/// Draws the values taken by `trace` between the ticks `first` and `last`:
+/// a high or low line for bits, a box with the value in hexadecimal for
+/// buses, and a middle line for undefined or floating bits.
+fn spawn_trace(
+    parent: &mut ChildSpawnerCommands,
+    trace: &Trace,
+    view: &WaveformView,
+    waveform: &Waveform,
+    (first, last): (u64, u64),
+    top: f32,
+) {
+    // The value at `first` is the last one set before it.
+    let start = trace
+        .changes
+        .partition_point(|(tick, _)| *tick <= first)
+        .saturating_sub(1);
+    for (index, (tick, signal)) in trace.changes.iter().enumerate().skip(start) {
+        if *tick >= last {
+            break;
+        }
+        let next = trace
+            .changes
+            .get(index + 1)
+            .map_or(waveform.end + 1, |(next, _)| *next);
+
+        let left = NAME_WIDTH + view.x((*tick).max(first), first);
+        let width = (view.x(next.min(last), first) + NAME_WIDTH - left).max(1.0);
+        let color = signal_color(signal);
+        if signal.width() > 1 {
+            let mut segment = parent.spawn((
+                Node {
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..rectangle(left, top + 3.0, width, ROW_HEIGHT - 7.0)
+                },
+                BorderColor::all(color),
+            ));
+            let text = signal.to_string();
+            if width > text.len() as f32 * CHAR_WIDTH + 4.0 {
+                segment.with_child(label(text, 2.0, 1.0, color));
+            }
+            continue;
+        }
+
+        let level = match (signal.value(), signal.is_high()) {
+            (Some(_), true) => top + 4.0,
+            (Some(_), false) => top + ROW_HEIGHT - 6.0,
+            (None, _) => top + ROW_HEIGHT / 2.0 - 1.0,
+        };
+        parent.spawn((rectangle(left, level, width, 2.0), BackgroundColor(color)));
+        if index > 0 && *tick >= first {
+            parent.spawn((
+                rectangle(left, top + 4.0, 1.0, ROW_HEIGHT - 8.0),
+                BackgroundColor(color),
+            ));
+        }
+    }
+}
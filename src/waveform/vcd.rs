@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    netlist::identifier,
+    signal::{Level, Signal},
+    waveform::Waveform,
+};
+
+/// The short code VCD uses to refer to the signal at `index`, written with
+/// the printable ASCII characters.
+fn code(mut index: usize) -> String {
+    const FIRST: u8 = b'!';
+    const COUNT: usize = (b'~' - b'!' + 1) as usize;
+
+    let mut code = String::new();
+    loop {
+        code.push((FIRST + (index % COUNT) as u8) as char);
+        index /= COUNT;
+        if index == 0 {
+            return code;
+        }
+        index -= 1;
+    }
+}
+
+/// A value change of the signal with the given code: a scalar for single
+/// bits, a binary vector otherwise.
+fn change(signal: Signal, width: u8, code: &str) -> String {
+    let digit = |index| match signal.level(index) {
+        Level::Low => '0',
+        Level::High => '1',
+        Level::Undefined => 'x',
+        Level::HighZ => 'z',
+    };
+    if width == 1 {
+        return format!("{}{code}", digit(0));
+    }
+    let bits = (0..signal.width()).rev().map(digit).collect::<String>();
+    format!("b{bits} {code}")
+}
+
+/// Writes a recording as a Value Change Dump, one time unit per tick from
+/// the start of the recording.
+pub fn write(waveform: &Waveform) -> String {
+    let mut vcd = String::new();
+    vcd.push_str("$version ergo $end\n");
+    vcd.push_str("$comment one time unit per simulation tick $end\n");
+    vcd.push_str("$timescale 1 ns $end\n");
+    vcd.push_str("$scope module circuit $end\n");
+    let widths = waveform
+        .traces
+        .iter()
+        .map(|trace| trace.width())
+        .collect::<Vec<_>>();
+    for (index, trace) in waveform.traces.iter().enumerate() {
+        vcd.push_str(&format!(
+            "$var wire {} {} {} $end\n",
+            widths[index],
+            code(index),
+            identifier(&trace.name)
+        ));
+    }
+    vcd.push_str("$upscope $end\n$enddefinitions $end\n");
+
+    let mut changes = BTreeMap::<u64, Vec<String>>::new();
+    for (index, trace) in waveform.traces.iter().enumerate() {
+        for (tick, signal) in &trace.changes {
+            let time = tick.saturating_sub(waveform.start);
+            let line = change(*signal, widths[index], &code(index));
+            changes.entry(time).or_default().push(line);
+        }
+    }
+
+    for (time, lines) in changes {
+        vcd.push_str(&format!("#{time}\n"));
+        for line in lines {
+            vcd.push_str(&line);
+            vcd.push('\n');
+        }
+    }
+    vcd.push_str(&format!(
+        "#{}\n",
+        waveform.end.saturating_sub(waveform.start)
+    ));
+    vcd
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Entity;
+
+    use super::*;
+    use crate::waveform::Trace;
+
+    #[test]
+    fn codes_are_short_and_distinct() {
+        assert_eq!(code(0), "!");
+        assert_eq!(code(93), "~");
+        assert_eq!(code(94), "!!");
+        let codes = (0..10_000)
+            .map(code)
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(codes.len(), 10_000);
+    }
+
+    #[test]
+    fn changes_are_scalars_or_vectors() {
+        assert_eq!(change(Signal::bit(true), 1, "!"), "1!");
+        assert_eq!(change(Signal::undefined(1), 1, "!"), "x!");
+        assert_eq!(change(Signal::new(4, 0b0101), 4, "\""), "b0101 \"");
+        assert_eq!(change(Signal::floating(2), 2, "#"), "bzz #");
+    }
+
+    #[test]
+    fn dumps_start_at_time_zero() {
+        let trace = |name: &str, changes| Trace {
+            entity: Entity::from_raw_u32(1).unwrap(),
+            name: name.to_string(),
+            changes,
+        };
+        let waveform = Waveform {
+            traces: vec![
+                trace(
+                    "clk",
+                    vec![(10, Signal::bit(false)), (11, Signal::bit(true))],
+                ),
+                trace("count out", vec![(10, Signal::new(2, 3))]),
+            ],
+            start: 10,
+            end: 13,
+            recording: false,
+        };
+        let vcd = write(&waveform);
+        assert!(vcd.contains("$var wire 1 ! clk $end\n$var wire 2 \" count_out $end\n"));
+        assert!(vcd.ends_with("$enddefinitions $end\n#0\n0!\nb11 \"\n#1\n1!\n#3\n"));
+    }
+}